@@ -49,13 +49,11 @@ fn main() {
         obj.insert("this_is_null", Expr::null());
         obj.insert("am_i_cute", true);
         obj.insert("created_at", Utc::now());
-        obj.insert("birthday", NaiveDate::from_ymd(2011, 7, 7));
+        obj.insert("birthday", NaiveDate::from_ymd_opt(2011, 7, 7).unwrap());
 
-        {
-            let mut obj2 = Object::default();
+        obj.insert_object("objective", |obj2| {
             obj2.insert("foo", "bar");
-            obj.insert("objective", obj2);
-        }
+        });
 
         Create::new(Ref::class("HouseCats"), obj)
     };
@@ -1,34 +1,134 @@
 //! Tools for communicating with Fauna.
 
+mod budget;
+mod field_path;
+mod in_flight;
+mod last_txn;
+mod record;
+mod redact;
 mod response;
+mod retry;
+mod schema;
+mod server_build;
+mod snapshot;
+mod transport;
 
 #[cfg(feature = "sync_client")]
 mod sync;
 
+#[cfg(feature = "v10")]
+mod fql;
+
+pub use record::Replay;
 pub use response::*;
+pub use retry::RetryPolicy;
+pub use snapshot::*;
+pub use transport::*;
+
+#[cfg(feature = "v10")]
+pub use fql::*;
 
 #[cfg(feature = "sync_client")]
 pub use sync::*;
 
 use crate::{
     error::{Error, FaunaErrors},
-    expr::Expr,
+    expr::{Array, Expr, Object, Ref},
+    query::{
+        aggregate::Count,
+        auth::Login,
+        basic::{Binding, Do, If, Let, Var},
+        collection::{Drop, Map, Take},
+        logical::{Equals, Exists},
+        misc::Abort,
+        read::{Get, KeyFromSecret, Paginate, Projection, Select},
+        set::{Match, Range},
+        write::{
+            Create, CreateDatabase, CreateKey, DatabaseParams, KeyParams, Role, Update,
+            UpdateParams,
+        },
+    },
 };
-use futures::{future, stream::Stream, Future};
-use http::header::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE};
-use hyper::{client::HttpConnector, Body, StatusCode, Uri};
+use budget::Budget;
+use chrono::{DateTime, Utc};
+use futures::{
+    future::{self, Loop},
+    Future,
+};
+use http::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE};
+use hyper::{Body, StatusCode, Uri};
 use hyper_tls::HttpsConnector;
+use in_flight::InFlight;
+use last_txn::LastTxn;
+use record::{RecordSink, Recording};
+use schema::{SchemaCallback, SchemaWatch};
+use serde::de::DeserializeOwned;
 use serde_json;
-use std::{borrow::Cow, time::Duration};
+use server_build::ServerBuild;
+use std::{
+    borrow::Cow,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio_timer::Timeout;
 
-type Transport = hyper::Client<HttpsConnector<HttpConnector>>;
+/// The largest page size Fauna allows.
+const MAX_PAGE_SIZE: usize = 100_000;
+
+/// The abort message of a failed `compare_and_update`.
+const CONFLICT_MESSAGE: &str = "conflict";
+
+/// The header identifying the driver, and the application if set.
+const DRIVER_HEADER: &str = "x-fauna-driver";
+
+const DRIVER: &str = concat!("faunadb-rust/", env!("CARGO_PKG_VERSION"));
+
+const API_VERSION_HEADER: &str = "x-faunadb-api-version";
+
+/// The API version used unless set in the builder.
+const DEFAULT_API_VERSION: &str = "2.1";
+
+/// The header for the time in milliseconds Fauna should run the query before
+/// cancelling it.
+const QUERY_TIMEOUT_HEADER: &str = "x-query-timeout";
+
+/// The path of the Fauna health check.
+const PING_PATH: &str = "/ping";
+
+/// What to do when a set has more elements than allowed, see
+/// [collect_set_as](struct.Client.html#method.collect_set_as).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OnOverflow {
+    /// Return the first elements up to the limit.
+    Truncate,
+    /// Fail with `Error::TooManyItems`.
+    Fail,
+}
+
+/// The format of the response data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// With the type annotations, such as `@ref` and `@ts`.
+    Tagged,
+    /// Plain JSON, the annotated values returned as strings and objects.
+    Simple,
+}
 
 /// For building a new Fauna client.
 pub struct ClientBuilder<'a> {
     uri: Cow<'a, str>,
     secret: Cow<'a, str>,
     timeout: Duration,
+    transport: Option<Box<dyn Transport>>,
+    budget: Budget,
+    tagged_output: bool,
+    on_schema_change: Option<SchemaCallback>,
+    record: Option<RecordSink>,
+    max_response_bytes: Option<usize>,
+    application_name: Option<Cow<'a, str>>,
+    api_version: Cow<'a, str>,
+    redact_logs: bool,
+    retry_policy: RetryPolicy,
 }
 
 impl<'a> ClientBuilder<'a> {
@@ -45,24 +145,166 @@ impl<'a> ClientBuilder<'a> {
         self
     }
 
+    /// If `false`, drops the type annotations (`@ref`, `@ts` etc.) from the
+    /// response, so refs, timestamps and other annotated values are returned as
+    /// plain strings and objects. The response is still requested in the tagged
+    /// format, so user keys starting with `@` are kept intact. Default: `true`.
+    pub fn tagged_output(&mut self, tagged: bool) -> &mut Self {
+        self.tagged_output = tagged;
+        self
+    }
+
+    /// Called with the previous and the new schema version when the
+    /// `X-Schema-Version` reported by Fauna changes between responses. The
+    /// first reported version is a change from `None`.
+    pub fn on_schema_change<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(Option<&str>, &str) + Send + Sync + 'static,
+    {
+        self.on_schema_change = Some(Box::new(f));
+        self
+    }
+
+    /// Calls the `sink` with the request payload and the response body of
+    /// every successful query, e.g. for building fixtures to serve with
+    /// [Replay](struct.Replay.html).
+    pub fn record<F>(&mut self, sink: F) -> &mut Self
+    where
+        F: Fn(&str, &str) + Send + Sync + 'static,
+    {
+        self.record = Some(Box::new(sink));
+        self
+    }
+
+    /// Use a custom [Transport](trait.Transport.html) for sending the
+    /// requests. Default: a `hyper` client over HTTPS.
+    pub fn transport(&mut self, transport: impl Transport + 'static) -> &mut Self {
+        self.transport = Some(Box::new(transport));
+        self
+    }
+
+    /// The maximum number of read ops a single query is allowed to use. A
+    /// query reported to go over the budget returns an error. Note that the
+    /// query has already been executed when the error is returned. Default:
+    /// no limit.
+    pub fn max_read_ops(&mut self, ops: u64) -> &mut Self {
+        self.budget.read_ops = Some(ops);
+        self
+    }
+
+    /// The maximum number of write ops a single query is allowed to use. A
+    /// query reported to go over the budget returns an error. Note that the
+    /// query has already been executed when the error is returned. Default:
+    /// no limit.
+    pub fn max_write_ops(&mut self, ops: u64) -> &mut Self {
+        self.budget.write_ops = Some(ops);
+        self
+    }
+
+    /// The maximum size of a response body. Reading a larger response is
+    /// stopped at the limit, returning an error, and a response with a
+    /// `Content-Length` over the limit is not read at all. Default: no limit.
+    pub fn max_response_bytes(&mut self, bytes: usize) -> &mut Self {
+        self.max_response_bytes = Some(bytes);
+        self
+    }
+
+    /// Appended to the driver name in the `X-Fauna-Driver` header, to tell
+    /// the traffic of the application apart in the Fauna dashboard.
+    pub fn application_name(&mut self, name: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.application_name = Some(name.into());
+        self
+    }
+
+    /// The Fauna API version of the queries, sent in the
+    /// `X-FaunaDB-API-Version` header. Default: `2.1`.
+    pub fn api_version(&mut self, version: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.api_version = version.into();
+        self
+    }
+
+    /// If enabled, passwords, secrets and credentials are masked in the
+    /// logged queries and responses. Default: `true`.
+    pub fn redact_logs(&mut self, redact: bool) -> &mut Self {
+        self.redact_logs = redact;
+        self
+    }
+
+    /// Retry the requests Fauna is too busy or unavailable to serve. The
+    /// gateway errors are retried only for the queries sent with
+    /// [query_idempotent](struct.Client.html#method.query_idempotent).
+    /// Default: no retries.
+    pub fn retry_policy(&mut self, policy: RetryPolicy) -> &mut Self {
+        self.retry_policy = policy;
+        self
+    }
+
     /// Creates the client.
     pub fn build(self) -> crate::Result<Client> {
-        let mut builder = hyper::Client::builder();
-        builder.keep_alive(true);
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => {
+                let mut builder = hyper::Client::builder();
+                builder.keep_alive(true);
+
+                Box::new(builder.build(HttpsConnector::new(1)?))
+            }
+        };
+
+        let transport: Arc<dyn Transport> = match self.record {
+            Some(sink) => Arc::new(Recording::new(
+                Arc::from(transport),
+                sink,
+                self.max_response_bytes,
+            )),
+            None => Arc::from(transport),
+        };
+
+        let driver = match self.application_name {
+            Some(name) => HeaderValue::from_str(&format!("{} {}", DRIVER, name))?,
+            None => HeaderValue::from_static(DRIVER),
+        };
+
+        let api_version = HeaderValue::from_str(&self.api_version)?;
 
         let secret_b64 = base64::encode(&format!("{}:", self.secret));
+        let uri: Uri = self.uri.parse()?;
 
         Ok(Client {
-            transport: builder.build(HttpsConnector::new(1)?),
-            uri: self.uri.parse()?,
+            transport,
             timeout: self.timeout,
+            budget: self.budget,
+            max_response_bytes: self.max_response_bytes,
+            tagged_output: self.tagged_output,
+            schema: Arc::new(SchemaWatch::new(self.on_schema_change)),
+            in_flight: Arc::new(InFlight::default()),
+            server_build: Arc::new(ServerBuild::default()),
+            last_txn: Arc::new(LastTxn::default()),
             authorization: format!("Basic {}", secret_b64),
+            driver,
+            api_version,
+            redact_logs: self.redact_logs,
+            retry_policy: self.retry_policy,
+            ping_uri: {
+                let mut parts = uri.clone().into_parts();
+                parts.path_and_query = Some(http::uri::PathAndQuery::from_static(PING_PATH));
+                Uri::from_parts(parts)?
+            },
+            #[cfg(feature = "v10")]
+            fql_uri: {
+                let mut parts = uri.clone().into_parts();
+                parts.path_and_query = Some(http::uri::PathAndQuery::from_static(fql::FQL_PATH));
+                Uri::from_parts(parts)?
+            },
+            uri,
+            #[cfg(feature = "v10")]
+            bearer: format!("Bearer {}", self.secret),
         })
     }
 
     #[cfg(feature = "sync_client")]
     pub fn build_sync(self) -> crate::Result<SyncClient> {
-        Ok(SyncClient::new(self.build()?)?)
+        SyncClient::new(self.build()?)
     }
 }
 
@@ -70,12 +312,30 @@ impl<'a> ClientBuilder<'a> {
 /// [ClientBuilder](struct.ClientBuilder.html).
 ///
 /// Do not create new clients for every request to prevent
-/// spamming Fauna servers with new connections.
+/// spamming Fauna servers with new connections. Clones of the client share the
+/// same connections.
+#[derive(Clone)]
 pub struct Client {
-    transport: Transport,
+    transport: Arc<dyn Transport>,
     uri: Uri,
     timeout: Duration,
+    budget: Budget,
+    max_response_bytes: Option<usize>,
+    tagged_output: bool,
+    schema: Arc<SchemaWatch>,
+    in_flight: Arc<InFlight>,
+    server_build: Arc<ServerBuild>,
+    last_txn: Arc<LastTxn>,
     authorization: String,
+    driver: HeaderValue,
+    api_version: HeaderValue,
+    redact_logs: bool,
+    retry_policy: RetryPolicy,
+    ping_uri: Uri,
+    #[cfg(feature = "v10")]
+    fql_uri: Uri,
+    #[cfg(feature = "v10")]
+    bearer: String,
 }
 
 impl Client {
@@ -86,6 +346,16 @@ impl Client {
             uri: Cow::from("https://db.fauna.com"),
             secret: secret.into(),
             timeout: Duration::new(60, 0),
+            transport: None,
+            budget: Budget::default(),
+            tagged_output: true,
+            on_schema_change: None,
+            record: None,
+            max_response_bytes: None,
+            application_name: None,
+            api_version: Cow::from(DEFAULT_API_VERSION),
+            redact_logs: true,
+            retry_policy: RetryPolicy::default(),
         }
     }
 
@@ -94,52 +364,689 @@ impl Client {
     where
         Q: Into<Expr<'a>>,
     {
-        let query = query.into();
-        let payload_json = serde_json::to_string(&query).unwrap();
+        match self.prepare_query(&query.into()) {
+            Ok(request) => self.send_query_request(request, self.timeout, self.tagged_output),
+            Err(e) => FutureResponse::err(e),
+        }
+    }
+
+    /// Send the `template` with the `bindings` bound as variables, so the
+    /// values can be referred to with [Var](../query/basic/struct.Var.html)
+    /// instead of building them into the query.
+    ///
+    /// Fails with `Error::RequestDataFailure` if the same variable is bound
+    /// more than once.
+    ///
+    /// ```no_run
+    /// # use faunadb::prelude::*;
+    /// let client = Client::builder("my_fauna_secret").build().unwrap();
+    /// let template = Get::by_match(Index::find("cats_by_name"), Var::new("name"));
+    ///
+    /// client.query_with_bindings(template, vec![("name", "Musti")]);
+    /// ```
+    pub fn query_with_bindings<'a, Q, B, V, E>(
+        &self,
+        template: Q,
+        bindings: B,
+    ) -> FutureResponse<Response>
+    where
+        Q: Into<Expr<'a>>,
+        B: IntoIterator<Item = (V, E)>,
+        V: Into<Cow<'a, str>>,
+        E: Into<Expr<'a>>,
+    {
+        let bindings = bindings
+            .into_iter()
+            .map(|(variable, expr)| Binding::new(variable, expr));
+
+        match Let::try_bindings(bindings, template) {
+            Ok(query) => self.query(query),
+            Err(e) => FutureResponse::err(e),
+        }
+    }
+
+    /// Like [query](#method.query), but waiting for the response at most for
+    /// the given `timeout` instead of the one set in the builder. Fauna is
+    /// asked to cancel the query after the timeout with the
+    /// `X-Query-Timeout` header.
+    pub fn query_with_timeout<'a, Q>(&self, query: Q, timeout: Duration) -> FutureResponse<Response>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        let mut request = match self.prepare_query(&query.into()) {
+            Ok(request) => request,
+            Err(e) => return FutureResponse::err(e),
+        };
+
+        request.headers_mut().insert(
+            QUERY_TIMEOUT_HEADER,
+            HeaderValue::from(timeout.as_millis() as u64),
+        );
+
+        self.send_query_request(request, timeout, self.tagged_output)
+    }
+
+    /// Like [query](#method.query), but marking the query safe to run more
+    /// than once, so the [retry policy](struct.RetryPolicy.html) also retries
+    /// it after a `502 Bad Gateway` or a `504 Gateway Timeout`.
+    ///
+    /// Only use with reads, or with writes that can be applied twice.
+    pub fn query_idempotent<'a, Q>(&self, query: Q) -> FutureResponse<Response>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        let mut request = match self.prepare_query(&query.into()) {
+            Ok(request) => request,
+            Err(e) => return FutureResponse::err(e),
+        };
+
+        request.extensions_mut().insert(retry::Idempotent);
+
+        self.send_query_request(request, self.timeout, self.tagged_output)
+    }
+
+    /// Serializes the query and builds the request, for the query methods to
+    /// adjust the headers or extensions of before sending.
+    fn prepare_query(&self, query: &Expr) -> crate::Result<hyper::Request<Body>> {
+        to_payload(query).map(|payload_json| self.prepare_payload(payload_json))
+    }
+
+    fn prepare_payload(&self, payload_json: String) -> hyper::Request<Body> {
+        trace!("Querying with: {:?}", self.loggable(&payload_json));
+
+        self.build_request(payload_json)
+    }
+
+    fn send_query(&self, payload_json: String) -> FutureResponse<Response> {
+        self.send_query_request(
+            self.prepare_payload(payload_json),
+            self.timeout,
+            self.tagged_output,
+        )
+    }
+
+    fn send_query_request(
+        &self,
+        request: hyper::Request<Body>,
+        timeout: Duration,
+        tagged_output: bool,
+    ) -> FutureResponse<Response> {
+        self.request_with_timeout(
+            request,
+            timeout,
+            move |status, headers, body| match status {
+                s if s.is_success() => parse_response(tagged_output, &headers, &body),
+                _ => Err(query_error(status, body)),
+            },
+        )
+    }
+
+    /// Send a query with the response in the given format, overriding the
+    /// [tagged_output](struct.ClientBuilder.html#method.tagged_output) of the
+    /// client.
+    pub fn query_with_format<'a, Q>(
+        &self,
+        query: Q,
+        format: OutputFormat,
+    ) -> FutureResponse<Response>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        match self.prepare_query(&query.into()) {
+            Ok(request) => {
+                self.send_query_request(request, self.timeout, format == OutputFormat::Tagged)
+            }
+            Err(e) => FutureResponse::err(e),
+        }
+    }
+
+    /// Like [query](#method.query), but also returns the time from sending
+    /// the query to getting the response, as seen by the client. Compared to
+    /// the `x-query-time` header reported by Fauna, the difference is the time
+    /// spent in the network.
+    pub fn query_timed<'a, Q>(&self, query: Q) -> FutureResponse<(Response, Duration)>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        let querying = self.query(query);
+
+        let timed = future::lazy(move || {
+            let started = Instant::now();
+            querying.map(move |response| (response, started.elapsed()))
+        });
+
+        FutureResponse(Box::new(timed))
+    }
+
+    /// Like [query](#method.query), but also returns the HTTP status and all
+    /// the headers of the response, for debugging.
+    pub fn query_detailed<'a, Q>(
+        &self,
+        query: Q,
+    ) -> FutureResponse<(Response, StatusCode, HeaderMap)>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        let request = match self.prepare_query(&query.into()) {
+            Ok(request) => request,
+            Err(e) => return FutureResponse::err(e),
+        };
+
+        let tagged_output = self.tagged_output;
+
+        self.request(request, move |status, headers, body| match status {
+            s if s.is_success() => {
+                let response = parse_response(tagged_output, &headers, &body)?;
+                Ok((response, status, headers))
+            }
+            _ => Err(query_error(status, body)),
+        })
+    }
+
+    /// A handle running the queries at the given point in time, for
+    /// consistent reads over several queries.
+    ///
+    /// ```no_run
+    /// # use faunadb::prelude::*;
+    /// # use chrono::Utc;
+    /// let client = Client::builder("my_fauna_secret").build().unwrap();
+    /// let snapshot = client.snapshot(Utc::now());
+    ///
+    /// snapshot.query(Get::instance(Ref::class("cats")));
+    /// snapshot.query(Get::instance(Ref::class("dogs")));
+    /// ```
+    pub fn snapshot(&self, timestamp: DateTime<Utc>) -> Snapshot<'_> {
+        Snapshot::new(self, timestamp)
+    }
+
+    /// The `X-FaunaDB-Build` of the server from the last response that had
+    /// one, shared with the clones of the client. Useful for bug reports.
+    pub fn server_version(&self) -> Option<String> {
+        self.server_build.last()
+    }
+
+    /// Waits for the requests in flight to finish and drops the client. The
+    /// connections are closed when the last clone of the client is dropped.
+    ///
+    /// Requests sent from the clones are waited for too, so the clones should
+    /// stop sending new requests before shutting down.
+    pub fn shutdown(self) -> FutureResponse<()> {
+        let drained = self.in_flight.drained().map(move |_| drop(self));
+        FutureResponse(Box::new(drained))
+    }
+
+    /// Opens a connection to Fauna by pinging the server, so the following
+    /// queries don't have to wait for the DNS lookup and the TLS handshake.
+    pub fn warm_up(&self) -> FutureResponse<()> {
+        let mut builder = hyper::Request::builder();
+
+        builder.uri(&self.ping_uri);
+        builder.method("GET");
+        builder.header(AUTHORIZATION, self.authorization.as_bytes());
+
+        self.request(
+            builder.body(Body::empty()).unwrap(),
+            |status, _, body| match status {
+                s if s.is_success() => Ok(()),
+                _ => Err(query_error(status, body)),
+            },
+        )
+    }
+
+    /// Send the queries in one request as an array, returning a response for
+    /// every query in the same order.
+    ///
+    /// The queries are executed in a single transaction, so if any of them
+    /// fails, the whole batch fails. The headers are reported once for the
+    /// request, so every response has the metrics of the whole batch.
+    pub fn query_all<'a, I, Q>(&self, queries: I) -> FutureResponse<Vec<Response>>
+    where
+        I: IntoIterator<Item = Q>,
+        Q: Into<Expr<'a>>,
+    {
+        let tagged_output = self.tagged_output;
+
+        let responses = self
+            .query_array(queries)
+            .and_then(move |(response, results)| {
+                results
+                    .into_iter()
+                    .map(|result| Ok(response.with_resource(from_tagged(tagged_output, result)?)))
+                    .collect::<crate::Result<Vec<_>>>()
+            });
+
+        FutureResponse(Box::new(responses))
+    }
+
+    /// Send the queries in one request, deserializing every result into `T`
+    /// independently. The type annotations are dropped from the results
+    /// before deserializing, so e.g. timestamps deserialize from strings.
+    ///
+    /// The queries are executed in a single transaction, so if any of them
+    /// fails, the whole batch fails. The per-query errors are from
+    /// deserializing the results.
+    pub fn query_batch_as<'a, T, Q>(&self, queries: Vec<Q>) -> FutureResponse<Vec<crate::Result<T>>>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+        Q: Into<Expr<'a>>,
+    {
+        let batch = self.query_array(queries).map(|(_, results)| {
+            results
+                .into_iter()
+                .map(|result| from_json(strip_annotations(result)))
+                .collect()
+        });
+
+        FutureResponse(Box::new(batch))
+    }
+
+    /// Sends the queries as an array, returning the response without the
+    /// resource and the results as the tagged JSON.
+    fn query_array<'a, I, Q>(
+        &self,
+        queries: I,
+    ) -> FutureResponse<(Response, Vec<serde_json::Value>)>
+    where
+        I: IntoIterator<Item = Q>,
+        Q: Into<Expr<'a>>,
+    {
+        let query = Expr::from(Array(queries.into_iter().map(Into::into).collect()));
+
+        let request = match self.prepare_query(&query) {
+            Ok(request) => request,
+            Err(e) => return FutureResponse::err(e),
+        };
 
-        trace!("Querying with: {:?}", &payload_json);
+        self.request(request, move |status, headers, body| match status {
+            s if s.is_success() => {
+                let mut json: serde_json::Value =
+                    serde_json::from_str(&body).map_err(unexpected_response)?;
 
-        self.request(self.build_request(payload_json), |body| {
-            serde_json::from_str(&body).unwrap()
+                let results = match json["resource"].take() {
+                    serde_json::Value::Array(results) => results,
+                    _ => {
+                        return Err(Error::ResponseDataFailure(
+                            "Batch result is not an array".into(),
+                        ))
+                    }
+                };
+
+                Ok((response_from_json(true, &headers, json)?, results))
+            }
+            _ => Err(query_error(status, body)),
         })
     }
 
+    /// Read the fields of the `projection` with a single `Get`, and
+    /// deserialize them into `T`.
+    pub fn project_as<T>(&self, projection: Projection) -> FutureResponse<T>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        let query = self
+            .query(projection)
+            .and_then(|response| from_value(&response.resource));
+
+        FutureResponse(Box::new(query))
+    }
+
+    /// Resolve the database and role the given key secret gives access to.
+    pub fn key_info(&self, secret: &str) -> FutureResponse<KeyInfo> {
+        let query = self
+            .query(KeyFromSecret::new(secret))
+            .and_then(|response| KeyInfo::from_value(&response.resource));
+
+        FutureResponse(Box::new(query))
+    }
+
+    /// Updates the instance matching the `terms` in the `unique_index` with
+    /// the `data`, or creates an instance of the `class_ref` with the `data`
+    /// if there's none, in one query.
+    ///
+    /// The index should be unique, so two concurrent upserts can't both
+    /// create an instance.
+    pub fn upsert<'a>(
+        &self,
+        unique_index: impl Into<Expr<'a>>,
+        terms: impl Into<Expr<'a>>,
+        class_ref: impl Into<Expr<'a>>,
+        data: impl Into<Expr<'a>>,
+    ) -> FutureResponse<Response> {
+        self.query(upsert(
+            unique_index.into(),
+            terms.into(),
+            class_ref.into(),
+            data.into(),
+        ))
+    }
+
+    /// Creates an instance of the `class_ref` with the `data`, returning only
+    /// the ref of the new instance.
+    pub fn create_returning_ref<'a>(
+        &self,
+        class_ref: impl Into<Expr<'a>>,
+        data: impl Into<Expr<'a>>,
+    ) -> FutureResponse<Ref<'static>> {
+        let query = Select::new(vec!["ref"], Create::new(class_ref, data));
+
+        let created = self.query(query).and_then(|response| {
+            response
+                .resource
+                .as_reference()
+                .cloned()
+                .ok_or(Error::ResponseDataFailure(
+                    "Create did not return a ref".into(),
+                ))
+        });
+
+        FutureResponse(Box::new(created))
+    }
+
+    /// Updates the instance with the `data` only if its `ts` is still
+    /// `expected_ts`, i.e. nobody changed it since it was read.
+    ///
+    /// If the instance was changed, the transaction is aborted and the error
+    /// has the [abort_message](../error/enum.Error.html#method.abort_message)
+    /// `"conflict"`.
+    pub fn compare_and_update<'a>(
+        &self,
+        reference: impl Into<Expr<'a>>,
+        expected_ts: u64,
+        data: impl Into<Expr<'a>>,
+    ) -> FutureResponse<Response> {
+        self.query(compare_and_update(
+            reference.into(),
+            expected_ts,
+            data.into(),
+        ))
+    }
+
+    /// Create a new database and a key with the given `role` for it in one
+    /// request. Returns the `Ref` of the database and the secret of the key.
+    pub fn create_database_with_key(
+        &self,
+        name: &str,
+        role: Role,
+    ) -> FutureResponse<(Ref<'static>, String)> {
+        let query = self
+            .query(database_with_key(name, role))
+            .and_then(|response| {
+                let reference = response.resource["ref"]
+                    .as_reference()
+                    .cloned()
+                    .ok_or(Error::ResponseDataFailure("Database has no ref".into()))?;
+
+                let secret = response.resource["secret"]
+                    .as_str()
+                    .map(String::from)
+                    .ok_or(Error::ResponseDataFailure("Key has no secret".into()))?;
+
+                Ok((reference, secret))
+            });
+
+        FutureResponse(Box::new(query))
+    }
+
+    /// A client with the same settings and connections, authenticating with
+    /// the `secret` instead.
+    pub fn with_secret(&self, secret: &str) -> Client {
+        let secret_b64 = base64::encode(&format!("{}:", secret));
+
+        Client {
+            authorization: format!("Basic {}", secret_b64),
+            #[cfg(feature = "v10")]
+            bearer: format!("Bearer {}", secret),
+            ..self.clone()
+        }
+    }
+
+    /// Create a new database with a server key in one request, returning a
+    /// client authenticated with the key. Used with an admin key when setting
+    /// up a new database, which otherwise has no key to query it with.
+    pub fn bootstrap(&self, name: &str) -> FutureResponse<Client> {
+        let client = self.clone();
+
+        let bootstrap = self
+            .create_database_with_key(name, Role::Server)
+            .map(move |(_, secret)| client.with_secret(&secret));
+
+        FutureResponse(Box::new(bootstrap))
+    }
+
+    /// Change the password in the credentials of the instance, returning the
+    /// updated instance.
+    pub fn change_password(&self, reference: Ref<'_>, password: &str) -> FutureResponse<Response> {
+        self.query(password_update(reference, password))
+    }
+
+    /// Change the password in the credentials of the instance and log in
+    /// with it in one transaction, returning the secret of the new token.
+    pub fn change_password_and_login(
+        &self,
+        reference: Ref<'_>,
+        password: &str,
+    ) -> FutureResponse<String> {
+        let query = self
+            .query(password_update_and_login(reference, password))
+            .and_then(|response| {
+                response
+                    .resource
+                    .as_str()
+                    .map(String::from)
+                    .ok_or(Error::ResponseDataFailure("Token has no secret".into()))
+            });
+
+        FutureResponse(Box::new(query))
+    }
+
+    /// Read the elements of the index between `from` and `to`, inclusive,
+    /// mapped with the `lambda`, following the pages until the end of the range.
+    /// The mapped elements are deserialized into `T`, with the type annotations
+    /// dropped as in [query_batch_as](#method.query_batch_as).
+    ///
+    /// The range is compared to the index values in order, see
+    /// [Range](../query/set/struct.Range.html).
+    ///
+    /// ```no_run
+    /// # use faunadb::prelude::*;
+    /// # let client = Client::builder("my_fauna_secret").build().unwrap();
+    /// // Indexed with the values `[data.cost, ref]`
+    /// let names = client.scan_range::<String>(
+    ///     Ref::index("spells_by_cost"),
+    ///     3,
+    ///     6,
+    ///     Lambda::new(
+    ///         Array::from(vec!["cost", "ref"]),
+    ///         Select::new(vec!["data", "name"], Get::instance(Var::new("ref"))),
+    ///     ),
+    /// );
+    /// ```
+    pub fn scan_range<'a, T>(
+        &self,
+        index: impl Into<Expr<'a>>,
+        from: impl Into<Expr<'a>>,
+        to: impl Into<Expr<'a>>,
+        lambda: impl Into<Expr<'a>>,
+    ) -> FutureResponse<Vec<T>>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        let page = Paginate::new(Range::new(Match::new(index), from, to));
+        let query = match to_json(&Expr::from(Map::new(page, lambda))) {
+            Ok(query) => query,
+            Err(e) => return FutureResponse::err(e),
+        };
+
+        self.follow_pages(query, "/collection", None)
+    }
+
+    /// The number of elements in the `set`, counted by Fauna without
+    /// returning the elements.
+    pub fn count_set<'a>(&self, set: impl Into<Expr<'a>>) -> FutureResponse<u64> {
+        let query = self.query(Count::new(set)).and_then(|response| {
+            response
+                .resource
+                .as_u64()
+                .ok_or(Error::ResponseDataFailure("Count is not a number".into()))
+        });
+
+        FutureResponse(Box::new(query))
+    }
+
+    /// Read all the elements of the `set`, following the pages, and
+    /// deserialize them into `T` as in [scan_range](#method.scan_range).
+    ///
+    /// Stops reading after `max_items` elements. If the set has more, the
+    /// result depends on `on_overflow`.
+    pub fn collect_set_as<'a, T>(
+        &self,
+        set: impl Into<Expr<'a>>,
+        max_items: usize,
+        on_overflow: OnOverflow,
+    ) -> FutureResponse<Vec<T>>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        let mut page = Paginate::new(set);
+        page.size(max_items.saturating_add(1).min(MAX_PAGE_SIZE) as u32);
+
+        let query = match to_json(&Expr::from(page)) {
+            Ok(query) => query,
+            Err(e) => return FutureResponse::err(e),
+        };
+
+        self.follow_pages(query, "", Some((max_items, on_overflow)))
+    }
+
+    /// Sends the query, following the pages of the `Paginate` at
+    /// `page_pointer` until the end of the set or the limit.
+    fn follow_pages<T>(
+        &self,
+        query: serde_json::Value,
+        page_pointer: &'static str,
+        limit: Option<(usize, OnOverflow)>,
+    ) -> FutureResponse<Vec<T>>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        let client = self.clone();
+
+        let pages = future::loop_fn((query, Vec::new()), move |(mut query, mut results)| {
+            client
+                .send_query(query.to_string())
+                .and_then(move |response| {
+                    let mut page = match response.resource {
+                        Value::Simple(SimpleValue::Object(page)) => page,
+                        _ => {
+                            return Err(Error::ResponseDataFailure("Page is not an object".into()))
+                        }
+                    };
+
+                    match page.remove("data") {
+                        Some(Value::Simple(SimpleValue::Array(data))) => {
+                            for value in data {
+                                results.push(from_value(&value)?);
+                            }
+                        }
+                        _ => return Err(Error::ResponseDataFailure("Page has no data".into())),
+                    }
+
+                    let after = page.remove("after");
+
+                    if let Some((max_items, on_overflow)) = limit {
+                        let more = results.len() > max_items || after.is_some();
+
+                        if results.len() >= max_items && more {
+                            match on_overflow {
+                                OnOverflow::Truncate => {
+                                    results.truncate(max_items);
+                                    return Ok(Loop::Break(results));
+                                }
+                                OnOverflow::Fail => return Err(Error::TooManyItems(max_items)),
+                            }
+                        }
+                    }
+
+                    match after {
+                        Some(after) => {
+                            let page_query = query
+                                .pointer_mut(page_pointer)
+                                .ok_or(Error::RequestDataFailure("No page in the query"))?;
+
+                            page_query["after"] = to_json(&Expr::from(after))?;
+
+                            Ok(Loop::Continue((query, results)))
+                        }
+                        None => Ok(Loop::Break(results)),
+                    }
+                })
+        });
+
+        FutureResponse(Box::new(pages))
+    }
+
+    /// Read a window of `limit` elements starting from `offset` from the given
+    /// `Set` or `Ref`. Returns the page with the window in its `data`.
+    ///
+    /// The page is fetched with a size of `offset + limit`, so the whole window
+    /// is included in the page the elements are dropped from.
+    pub fn read_window<'a>(
+        &self,
+        set: impl Into<Expr<'a>>,
+        offset: u32,
+        limit: u32,
+    ) -> FutureResponse<Response> {
+        self.query(window(set, offset, limit))
+    }
+
     fn request<F, T>(&self, request: hyper::Request<Body>, f: F) -> FutureResponse<T>
     where
         T: Send + Sync + 'static,
-        F: FnOnce(String) -> T + Send + Sync + 'static,
+        F: FnOnce(StatusCode, HeaderMap, String) -> crate::Result<T> + Send + Sync + 'static,
+    {
+        self.request_with_timeout(request, self.timeout, f)
+    }
+
+    fn request_with_timeout<F, T>(
+        &self,
+        request: hyper::Request<Body>,
+        timeout: Duration,
+        f: F,
+    ) -> FutureResponse<T>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(StatusCode, HeaderMap, String) -> crate::Result<T> + Send + Sync + 'static,
     {
-        let send_request = self
-            .transport
-            .request(request)
-            .map_err(|e| Error::ConnectionError(e.into()));
+        let in_flight = self.in_flight.start();
+        let send_request = self.retry_policy.send(self.transport.clone(), request);
+        let budget = self.budget;
+        let max_response_bytes = self.max_response_bytes;
+        let schema = self.schema.clone();
+        let server_build = self.server_build.clone();
+        let last_txn = self.last_txn.clone();
+        let redact_logs = self.redact_logs;
 
         let requesting = send_request.and_then(move |response| {
             trace!("Client::call got response status {}", response.status());
 
-            let status = response.status();
+            let (parts, body) = response.into_parts();
+            let status = parts.status;
+            let headers = parts.headers;
+            let within_budget = budget.check(&headers);
 
-            let get_body = response
-                .into_body()
-                .map_err(|e| Error::ConnectionError(e.into()))
-                .concat2();
+            schema.observe(&headers);
+            server_build.observe(&headers);
+            last_txn.observe(&headers);
 
-            get_body.and_then(move |body_chunk| {
-                if let Ok(body) = String::from_utf8(body_chunk.to_vec()) {
-                    trace!("Got response: {:?}", &body);
+            let get_body = transport::read_body(&headers, body, max_response_bytes);
 
-                    match status {
-                        s if s.is_success() => future::ok(f(body)),
-                        StatusCode::UNAUTHORIZED => future::err(Error::Unauthorized),
-                        StatusCode::BAD_REQUEST => {
-                            let errors: FaunaErrors = serde_json::from_str(&body).unwrap();
-                            future::err(Error::BadRequest(errors))
-                        }
-                        StatusCode::NOT_FOUND => {
-                            let errors: FaunaErrors = serde_json::from_str(&body).unwrap();
-                            future::err(Error::NotFound(errors))
-                        }
-                        _ => future::err(Error::DatabaseError(body)),
+            get_body.and_then(move |body| {
+                if let Ok(body) = String::from_utf8(body) {
+                    trace!("Got response: {:?}", loggable(redact_logs, &body));
+
+                    match within_budget {
+                        Err(e) if status.is_success() => future::err(e),
+                        _ => future::result(f(status, headers, body)),
                     }
                 } else {
                     future::err(Error::EmptyResponse)
@@ -147,8 +1054,8 @@ impl Client {
             })
         });
 
-        let with_timeout = Timeout::new(requesting, self.timeout).map_err(|e| {
-            if e.is_timer() {
+        let with_timeout = Timeout::new(requesting, timeout).map_err(|e| {
+            if e.is_elapsed() || e.is_timer() {
                 Error::TimeoutError
             } else {
                 match e.into_inner() {
@@ -158,7 +1065,16 @@ impl Client {
             }
         });
 
-        FutureResponse(Box::new(with_timeout))
+        let tracked = with_timeout.then(move |result| {
+            drop(in_flight);
+            result
+        });
+
+        FutureResponse(Box::new(tracked))
+    }
+
+    fn loggable<'p>(&self, payload: &'p str) -> Cow<'p, str> {
+        loggable(self.redact_logs, payload)
     }
 
     fn build_request(&self, payload: String) -> hyper::Request<Body> {
@@ -170,8 +1086,1697 @@ impl Client {
         builder.header(CONTENT_LENGTH, format!("{}", payload.len()).as_bytes());
         builder.header(CONTENT_TYPE, "application/json");
         builder.header(AUTHORIZATION, self.authorization.as_bytes());
-        builder.header("X-FaunaDB-API-Version", "2.1");
+        builder.header(API_VERSION_HEADER, self.api_version.clone());
+        builder.header(DRIVER_HEADER, self.driver.clone());
+
+        if let Some(txn_time) = self.last_txn.get() {
+            builder.header(
+                last_txn::LAST_SEEN_TXN_HEADER,
+                txn_time.to_string().as_str(),
+            );
+        }
 
         builder.body(Body::from(payload)).unwrap()
     }
 }
+
+fn database_with_key(name: &str, role: Role) -> Let<'_> {
+    let key = KeyParams::new(Select::new(vec!["ref"], Var::new("database")), role);
+
+    let mut result = Object::default();
+    result.insert("ref", Select::new(vec!["ref"], Var::new("database")));
+    result.insert("secret", Select::new(vec!["secret"], Var::new("key")));
+
+    Let::bindings(
+        vec![
+            Binding::new("database", CreateDatabase::new(DatabaseParams::new(name))),
+            Binding::new("key", CreateKey::new(key)),
+        ],
+        result,
+    )
+}
+
+fn upsert<'a>(index: Expr<'a>, terms: Expr<'a>, class_ref: Expr<'a>, data: Expr<'a>) -> If<'a> {
+    let exists = Exists::by_match(index.clone(), terms.clone());
+    let reference = Select::new(vec!["ref"], Get::by_match(index, terms));
+
+    let mut params = UpdateParams::new();
+    params.data(data.clone());
+
+    If::cond(
+        exists,
+        Update::new(reference, params),
+        Create::new(class_ref, data),
+    )
+}
+
+fn compare_and_update<'a>(reference: Expr<'a>, expected_ts: u64, data: Expr<'a>) -> If<'a> {
+    let current_ts = Select::new(vec!["ts"], Get::instance(reference.clone()));
+
+    let mut params = UpdateParams::new();
+    params.data(data);
+
+    If::cond(
+        Equals::new(current_ts, expected_ts),
+        Update::new(reference, params),
+        Abort::new(CONFLICT_MESSAGE),
+    )
+}
+
+fn password_update<'a>(reference: Ref<'a>, password: &'a str) -> Update<'a> {
+    let mut params = UpdateParams::new();
+    params.password(password);
+
+    Update::new(reference, params)
+}
+
+fn password_update_and_login<'a>(reference: Ref<'a>, password: &'a str) -> Do<'a> {
+    let login = Login::new(reference.clone(), password);
+
+    let mut query = Do::new(password_update(reference, password));
+    query.push(Select::new(vec!["secret"], login));
+
+    query
+}
+
+fn window<'a>(set: impl Into<Expr<'a>>, offset: u32, limit: u32) -> Take<'a> {
+    let mut page = Paginate::new(set);
+    page.size(offset.saturating_add(limit));
+
+    Take::new(limit, Drop::new(offset, page))
+}
+
+fn parse_response(tagged_output: bool, headers: &HeaderMap, body: &str) -> crate::Result<Response> {
+    let json = serde_json::from_str(body).map_err(unexpected_response)?;
+    response_from_json(tagged_output, headers, json)
+}
+
+fn response_from_json(
+    tagged_output: bool,
+    headers: &HeaderMap,
+    json: serde_json::Value,
+) -> crate::Result<Response> {
+    let mut response: Response = from_tagged(tagged_output, json)?;
+
+    response.schema_version = schema::schema_version(headers);
+    response.server_build = server_build::server_build(headers);
+    response.metrics = QueryMetrics::from_headers(headers);
+
+    Ok(response)
+}
+
+/// A response body that could not be parsed, keeping the reason and the
+/// position from serde.
+/// Deserializes the tagged JSON of a response, dropping the type annotations
+/// first unless `tagged_output`.
+fn from_tagged<T>(tagged_output: bool, json: serde_json::Value) -> crate::Result<T>
+where
+    T: DeserializeOwned,
+{
+    let json = if tagged_output {
+        json
+    } else {
+        strip_annotations(json)
+    };
+
+    serde_json::from_value(json).map_err(unexpected_response)
+}
+
+fn unexpected_response(e: serde_json::Error) -> Error {
+    Error::ResponseDataFailure(format!("Unexpected response body: {}", e).into())
+}
+
+/// The payload or the body as it should be written to the logs.
+fn loggable(redact_logs: bool, json: &str) -> Cow<'_, str> {
+    if redact_logs {
+        redact::redact(json)
+    } else {
+        Cow::from(json)
+    }
+}
+
+/// Serializes the query for the request payload.
+fn to_payload(query: &Expr) -> crate::Result<String> {
+    serde_json::to_string(query)
+        .map_err(|_| Error::RequestDataFailure("Query could not be serialized"))
+}
+
+fn to_json(query: &Expr) -> crate::Result<serde_json::Value> {
+    serde_json::to_value(query)
+        .map_err(|_| Error::RequestDataFailure("Query could not be serialized"))
+}
+
+/// Deserializes the value into `T`, dropping the type annotations.
+pub(crate) fn from_value<T>(value: &Value) -> crate::Result<T>
+where
+    T: DeserializeOwned,
+{
+    let json = serde_json::to_value(value)
+        .map_err(|e| Error::ConversionError(format!("Value could not be serialized: {}", e)))?;
+
+    from_json(strip_annotations(json))
+}
+
+/// Deserializes the plain JSON into `T`, naming the failed field in the error.
+fn from_json<T>(json: serde_json::Value) -> crate::Result<T>
+where
+    T: DeserializeOwned,
+{
+    field_path::from_json(&json).map_err(|(e, path)| match path {
+        Some(path) => Error::ConversionError(format!("{} at `{}`", e, path)),
+        None => Error::ConversionError(e.to_string()),
+    })
+}
+
+fn query_error(status: StatusCode, body: String) -> Error {
+    match status {
+        StatusCode::UNAUTHORIZED => Error::Unauthorized,
+        StatusCode::BAD_REQUEST | StatusCode::NOT_FOUND => {
+            match serde_json::from_str::<FaunaErrors>(&body) {
+                Ok(errors) if status == StatusCode::BAD_REQUEST => Error::BadRequest(errors),
+                Ok(errors) => Error::NotFound(errors),
+                Err(_) => Error::DatabaseError(body),
+            }
+        }
+        _ => Error::DatabaseError(body),
+    }
+}
+
+/// Replaces the annotated values, such as `{"@ts": "..."}`, with the value
+/// they annotate.
+fn strip_annotations(json: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value as Json;
+
+    match json {
+        Json::Object(mut map) => {
+            let annotation = match map.keys().next() {
+                Some(key) if map.len() == 1 && key.starts_with('@') => Some(key.clone()),
+                _ => None,
+            };
+
+            match annotation {
+                Some(ref key) if key == "@obj" => match map.remove(key) {
+                    Some(Json::Object(inner)) => Json::Object(
+                        inner
+                            .into_iter()
+                            .map(|(k, v)| (k, strip_annotations(v)))
+                            .collect(),
+                    ),
+                    Some(inner) => strip_annotations(inner),
+                    None => Json::Null,
+                },
+                Some(ref key) => strip_annotations(map.remove(key).unwrap_or(Json::Null)),
+                None => Json::Object(
+                    map.into_iter()
+                        .map(|(k, v)| (k, strip_annotations(v)))
+                        .collect(),
+                ),
+            }
+        }
+        Json::Array(values) => Json::Array(values.into_iter().map(strip_annotations).collect()),
+        json => json,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, prelude::*, test_utils::*};
+    use futures::{Future, Stream};
+    use serde_json::{self, json};
+    use std::sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn test_database_with_key() {
+        let query = Query::from(super::database_with_key("cats", Role::Server));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "let": {
+                "database": {
+                    "create_database": {
+                        "object": {"name": "cats", "api_version": "2.0"}
+                    }
+                },
+                "key": {
+                    "create_key": {
+                        "object": {
+                            "database": {"select": ["ref"], "from": {"var": "database"}},
+                            "role": "server",
+                            "priority": 1,
+                        }
+                    }
+                }
+            },
+            "in": {
+                "object": {
+                    "ref": {"select": ["ref"], "from": {"var": "database"}},
+                    "secret": {"select": ["secret"], "from": {"var": "key"}},
+                }
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_create_database_with_key_eval() {
+        with_scoped_client(|client, _| {
+            let db_name = gen_db_name();
+
+            let (reference, secret) = client
+                .create_database_with_key(&db_name, Role::Server)
+                .unwrap();
+
+            assert_eq!(Ref::database(&db_name).path(), reference.path());
+
+            let info = client.key_info(&secret).unwrap();
+
+            assert_eq!(Role::Server, info.role);
+            assert_eq!(reference.path(), info.database.unwrap().path());
+        })
+    }
+
+    #[test]
+    fn test_scan_range_follows_pages() {
+        let ref_json = |id: &str| json!({"@ref": {"id": id, "class": {"@ref": {"id": "cats", "class": {"@ref": {"id": "classes"}}}}}});
+
+        let first = json!({
+            "resource": {"data": [3, 4], "after": [5, ref_json("5")]}
+        });
+
+        let second = json!({ "resource": {"data": [5, 6]} });
+
+        let transport = MockTransport::new(200, first.to_string()).then(second.to_string());
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        let lambda = Lambda::new(Array::from(vec!["n", "ref"]), Var::new("n"));
+        let results: Vec<u64> = client
+            .scan_range(Ref::index("cats_by_n"), 3, 6, lambda)
+            .unwrap();
+
+        assert_eq!(vec![3, 4, 5, 6], results);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(2, requests.len());
+
+        let first_query: serde_json::Value = serde_json::from_str(requests[0].body()).unwrap();
+        let second_query: serde_json::Value = serde_json::from_str(requests[1].body()).unwrap();
+
+        assert_eq!(json!(3), first_query["collection"]["paginate"]["from"]);
+        assert_eq!(json!(6), first_query["collection"]["paginate"]["to"]);
+        assert_eq!(serde_json::Value::Null, first_query["collection"]["after"]);
+        assert_eq!(
+            json!([5, ref_json("5")]),
+            second_query["collection"]["after"]
+        );
+    }
+
+    fn paged_client(limit: usize, on_overflow: OnOverflow) -> crate::Result<Vec<u64>> {
+        let first = json!({"resource": {"data": [1, 2], "after": [3]}});
+        let second = json!({"resource": {"data": [3, 4]}});
+
+        let transport = MockTransport::new(200, first.to_string()).then(second.to_string());
+        let client = mock_client(transport, |_| ());
+
+        client.collect_set_as(Match::all(Ref::index("numbers")), limit, on_overflow)
+    }
+
+    #[test]
+    fn test_count_set() {
+        let transport = MockTransport::new(200, r#"{"resource": 3}"#);
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        assert_eq!(
+            3,
+            client
+                .count_set(Match::all(Index::find("all_cats")))
+                .unwrap()
+        );
+
+        let payload: serde_json::Value =
+            serde_json::from_str(requests.lock().unwrap()[0].body()).unwrap();
+
+        assert_eq!(json!({"count": {"match": {"index": "all_cats"}}}), payload);
+    }
+
+    #[test]
+    fn test_count_set_eval() {
+        with_class(|client, class_name| {
+            let index_name = gen_db_name();
+
+            client
+                .query(CreateIndex::new(IndexParams::new(
+                    &index_name,
+                    Ref::class(class_name),
+                )))
+                .unwrap();
+
+            for name in &["Musti", "Naukio", "Pelle"] {
+                let mut data = Object::default();
+                data.insert("name", *name);
+
+                client
+                    .query(Create::new(Ref::class(class_name), data))
+                    .unwrap();
+            }
+
+            let count = client
+                .count_set(Match::all(Ref::index(&index_name)))
+                .unwrap();
+
+            assert_eq!(3, count);
+        });
+    }
+
+    #[test]
+    fn test_collect_set_as() {
+        assert_eq!(vec![1, 2, 3, 4], paged_client(4, OnOverflow::Fail).unwrap());
+        assert_eq!(
+            vec![1, 2, 3, 4],
+            paged_client(10, OnOverflow::Fail).unwrap()
+        );
+        assert_eq!(
+            vec![1, 2, 3],
+            paged_client(3, OnOverflow::Truncate).unwrap()
+        );
+        assert_eq!(vec![1, 2], paged_client(2, OnOverflow::Truncate).unwrap());
+
+        match paged_client(3, OnOverflow::Fail) {
+            Err(Error::TooManyItems(3)) => (),
+            res => panic!("Expected too many items, got {:?}", res),
+        }
+
+        match paged_client(2, OnOverflow::Fail) {
+            Err(Error::TooManyItems(2)) => (),
+            res => panic!("Expected too many items, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_collect_set_as_page_size() {
+        let transport = MockTransport::new(200, r#"{"resource": {"data": []}}"#);
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        let results: Vec<u64> = client
+            .collect_set_as(Match::all(Ref::index("numbers")), 10, OnOverflow::Fail)
+            .unwrap();
+
+        assert!(results.is_empty());
+
+        let query: serde_json::Value =
+            serde_json::from_str(requests.lock().unwrap()[0].body()).unwrap();
+
+        assert_eq!(json!(11), query["size"]);
+    }
+
+    #[test]
+    fn test_scan_range_eval() {
+        with_class(|client, class_name| {
+            let index_name = gen_db_name();
+
+            let mut params = IndexParams::new(&index_name, Ref::class(class_name));
+            params.values(vec![
+                IndexValue::field(vec!["data", "n"]),
+                IndexValue::field(vec!["ref"]),
+            ]);
+
+            client.query(CreateIndex::new(params)).unwrap();
+
+            for n in 0..10 {
+                let mut data = Object::default();
+                data.insert("n", n);
+
+                client
+                    .query(Create::new(Ref::class(class_name), data))
+                    .unwrap();
+            }
+
+            let lambda = Lambda::new(Array::from(vec!["n", "ref"]), Var::new("n"));
+            let results: Vec<u64> = client
+                .scan_range(Ref::index(&index_name), 3, 6, lambda)
+                .unwrap();
+
+            assert_eq!(vec![3, 4, 5, 6], results);
+        })
+    }
+
+    #[test]
+    fn test_warm_up() {
+        let transport = MockTransport::new(200, r#"{"resource": "Scope write is OK"}"#);
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        client.warm_up().unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(http::Method::GET, requests[0].method());
+        assert_eq!("/ping", requests[0].uri().path());
+    }
+
+    #[test]
+    fn test_warm_up_eval() {
+        CLIENT.warm_up().unwrap();
+    }
+
+    #[test]
+    fn test_bootstrap() {
+        let transport = MockTransport::new(
+            200,
+            json!({"resource": {
+                "ref": {"@ref": {"id": "bootstrapped", "class": {"@ref": {"id": "databases"}}}},
+                "secret": "s3cr3t",
+            }})
+            .to_string(),
+        );
+
+        let requests = transport.requests();
+        let admin = mock_client(transport, |_| ());
+        let client = admin.bootstrap("bootstrapped").unwrap();
+
+        client.query("meow").unwrap();
+
+        let requests = requests.lock().unwrap();
+        let authorization = |i: usize| requests[i].headers()["authorization"].clone();
+
+        assert_eq!("Basic c2VjcmV0Og==", authorization(0));
+        assert_eq!("Basic czNjcjN0Og==", authorization(1));
+    }
+
+    #[test]
+    fn test_bootstrap_eval() {
+        with_scoped_client(|admin, _| {
+            let client = admin.bootstrap(&gen_db_name()).unwrap();
+
+            let class_name = gen_db_name();
+            client
+                .query(CreateClass::new(ClassParams::new(&class_name)))
+                .unwrap();
+
+            let mut data = Object::default();
+            data.insert("meow", true);
+
+            let response = client
+                .query(Create::new(Ref::class(&class_name), data))
+                .unwrap();
+
+            assert_eq!(Some(true), response.resource["data"]["meow"].as_bool());
+        })
+    }
+
+    #[test]
+    fn test_password_update_and_login() {
+        let query = Query::from(super::password_update_and_login(
+            Ref::instance("musti"),
+            "meowmeow",
+        ));
+
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "do": [
+                {
+                    "update": {"@ref": {"id": "musti"}},
+                    "params": {
+                        "object": {
+                            "credentials": {"object": {"password": "meowmeow"}}
+                        }
+                    }
+                },
+                {
+                    "select": ["secret"],
+                    "from": {
+                        "login": {"@ref": {"id": "musti"}},
+                        "params": {"object": {"password": "meowmeow"}}
+                    }
+                }
+            ]
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_change_password_and_login_eval() {
+        with_class(|client, class_name| {
+            let mut data = Object::default();
+            data.insert("name", "Musti");
+
+            let response = client
+                .query(Create::new(Ref::class(class_name), data))
+                .unwrap();
+
+            let reference = response.resource.get_reference().unwrap().clone();
+
+            let secret = client
+                .change_password_and_login(reference.clone(), "meowmeow")
+                .unwrap();
+
+            let identity = client
+                .with_secret(&secret)
+                .unwrap()
+                .query(Identity::new())
+                .unwrap();
+
+            assert_eq!(
+                reference.path(),
+                identity.resource.as_reference().unwrap().path()
+            );
+
+            assert!(client.change_password(reference, "purrpurr").is_ok());
+        })
+    }
+
+    #[test]
+    fn test_window() {
+        let query = Query::from(super::window(Classes::all(), 10, 5));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "take": 5,
+            "collection": {
+                "drop": 10,
+                "collection": {
+                    "paginate": {"classes": null},
+                    "size": 15,
+                    "events": false,
+                    "sources": false,
+                }
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_read_window_eval() {
+        with_class(|client, class_name| {
+            let index_name = gen_db_name();
+
+            let mut params = IndexParams::new(&index_name, Ref::class(class_name));
+            params.values(vec![IndexValue::field(vec!["data", "i"])]);
+
+            client.query(CreateIndex::new(params)).unwrap();
+
+            for i in 0..10 {
+                let mut data = Object::default();
+                data.insert("i", i);
+
+                client
+                    .query(Create::new(Ref::class(class_name), data))
+                    .unwrap();
+            }
+
+            let response = client
+                .read_window(Match::new(Ref::index(&index_name)), 3, 4)
+                .unwrap();
+
+            let window: Vec<u64> = response.resource["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|i| i.as_u64().unwrap())
+                .collect();
+
+            assert_eq!(vec![3, 4, 5, 6], window);
+        })
+    }
+
+    const TAGGED_RESPONSE: &str = r#"{
+        "resource": {
+            "ref": {"@ref": {"id": "1", "class": {"@ref": {"id": "cats", "class": {"@ref": {"id": "classes"}}}}}},
+            "ts": 1,
+            "data": {
+                "born": {"@ts": "2015-02-20T06:30:00Z"},
+                "escaped": {"@obj": {"@name": "Musti"}}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_strip_annotations() {
+        let json = serde_json::from_str(TAGGED_RESPONSE).unwrap();
+
+        let expected = json!({
+            "resource": {
+                "ref": {"id": "1", "class": {"id": "cats", "class": {"id": "classes"}}},
+                "ts": 1,
+                "data": {
+                    "born": "2015-02-20T06:30:00Z",
+                    "escaped": {"@name": "Musti"}
+                }
+            }
+        });
+
+        assert_eq!(expected, super::strip_annotations(json));
+    }
+
+    #[test]
+    fn test_tagged_output() {
+        let transport = MockTransport::new(200, TAGGED_RESPONSE);
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        let response = client.query("meow").unwrap();
+
+        assert!(response.resource["ref"].is_reference());
+        assert!(response.resource["data"]["born"].is_timestamp());
+        assert_eq!(
+            Some("Musti"),
+            response.resource["data"]["escaped"]["@name"].as_str()
+        );
+        assert!(requests.lock().unwrap()[0]
+            .headers()
+            .get("X-Format")
+            .is_none());
+    }
+
+    #[test]
+    fn test_simple_output() {
+        let transport = MockTransport::new(200, TAGGED_RESPONSE);
+        let requests = transport.requests();
+
+        let client = mock_client(transport, |builder| {
+            builder.tagged_output(false);
+        });
+
+        let response = client.query("meow").unwrap();
+
+        assert_eq!(Some("1"), response.resource["ref"]["id"].as_str());
+        assert_eq!(
+            Some("2015-02-20T06:30:00Z"),
+            response.resource["data"]["born"].as_str()
+        );
+        assert_eq!(
+            Some("Musti"),
+            response.resource["data"]["escaped"]["@name"].as_str()
+        );
+        assert!(requests.lock().unwrap()[0]
+            .headers()
+            .get("X-Format")
+            .is_none());
+    }
+
+    #[test]
+    fn test_simple_output_keeps_user_annotation_keys() {
+        let transport = MockTransport::new(
+            200,
+            r#"{"resource": {"@obj": {"@name": "Musti", "@ts": "yesterday"}}}"#,
+        );
+
+        let client = mock_client(transport, |builder| {
+            builder.tagged_output(false);
+        });
+
+        let response = client.query("meow").unwrap();
+
+        assert_eq!(
+            json!({"@name": "Musti", "@ts": "yesterday"}),
+            serde_json::to_value(&response.resource).unwrap()
+        );
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Cat {
+        name: String,
+        age: u64,
+    }
+
+    #[test]
+    fn test_query_with_bindings() {
+        let transport = MockTransport::new(200, r#"{"resource": "Musti"}"#);
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        let template = Get::by_match(Index::find("cats_by_name"), Var::new("name"));
+        let mut bindings = std::collections::HashMap::new();
+        bindings.insert("name", "Musti");
+
+        client.query_with_bindings(template, bindings).unwrap();
+
+        let payload: serde_json::Value =
+            serde_json::from_str(requests.lock().unwrap()[0].body()).unwrap();
+
+        let expected = json!({
+            "let": {"name": "Musti"},
+            "in": {"get": {"match": {"index": "cats_by_name"}, "terms": {"var": "name"}}}
+        });
+
+        assert_eq!(expected, payload);
+
+        match client.query_with_bindings(Var::new("a"), vec![("a", 1), ("a", 2)]) {
+            Err(Error::RequestDataFailure(_)) => (),
+            res => panic!("Expected a duplicate binding error, got {:?}", res),
+        }
+
+        assert_eq!(1, requests.lock().unwrap().len());
+    }
+
+    #[test]
+    fn test_upsert() {
+        let mut data = Object::default();
+        data.insert("name", "Musti");
+
+        let query = super::upsert(
+            Expr::from(Ref::index("cats_by_name")),
+            Expr::from("Musti"),
+            Expr::from(Ref::class("cats")),
+            Expr::from(data),
+        );
+
+        let expected = json!({
+            "if": {
+                "exists": {"match": {"@ref": {"index": {"@ref": {"id": "indexes"}}, "id": "cats_by_name"}}, "terms": "Musti"}
+            },
+            "then": {
+                "update": {
+                    "select": ["ref"],
+                    "from": {"get": {"match": {"@ref": {"index": {"@ref": {"id": "indexes"}}, "id": "cats_by_name"}}, "terms": "Musti"}}
+                },
+                "params": {"object": {"data": {"object": {"name": "Musti"}}}}
+            },
+            "else": {
+                "create": {"@ref": {"class": {"@ref": {"id": "classes"}}, "id": "cats"}},
+                "params": {"object": {"data": {"object": {"name": "Musti"}}}}
+            }
+        });
+
+        assert_eq!(expected, serde_json::to_value(Query::from(query)).unwrap());
+    }
+
+    #[test]
+    fn test_upsert_eval() {
+        with_class(|client, class_name| {
+            let index_name = gen_db_name();
+
+            let mut params = IndexParams::new(&index_name, Ref::class(class_name));
+            params.terms(vec![Term::field(vec!["data", "name"])]);
+            params.unique();
+
+            client.query(CreateIndex::new(params)).unwrap();
+
+            let mut data = Object::default();
+            data.insert("name", "Musti");
+            data.insert("age", 6);
+
+            let created = client
+                .upsert(
+                    Ref::index(&index_name),
+                    "Musti",
+                    Ref::class(class_name),
+                    data,
+                )
+                .unwrap();
+
+            let mut data = Object::default();
+            data.insert("name", "Musti");
+            data.insert("age", 7);
+
+            let updated = client
+                .upsert(
+                    Ref::index(&index_name),
+                    "Musti",
+                    Ref::class(class_name),
+                    data,
+                )
+                .unwrap();
+
+            assert_eq!(
+                created.resource["ref"].as_reference().unwrap().path(),
+                updated.resource["ref"].as_reference().unwrap().path()
+            );
+
+            assert_eq!(Some(7), updated.resource["data"]["age"].as_u64());
+        });
+    }
+
+    #[test]
+    fn test_compare_and_update() {
+        let mut data = Object::default();
+        data.insert("age", 8);
+
+        let query =
+            super::compare_and_update(Expr::from(Ref::instance("musti")), 1, Expr::from(data));
+
+        let expected = json!({
+            "if": {
+                "equals": [
+                    {"select": ["ts"], "from": {"get": {"@ref": {"id": "musti"}}}},
+                    1
+                ]
+            },
+            "then": {
+                "update": {"@ref": {"id": "musti"}},
+                "params": {"object": {"data": {"object": {"age": 8}}}}
+            },
+            "else": {"abort": "conflict"}
+        });
+
+        assert_eq!(expected, serde_json::to_value(Query::from(query)).unwrap());
+    }
+
+    #[test]
+    fn test_create_returning_ref() {
+        let transport = MockTransport::new(
+            200,
+            r#"{"resource": {"@ref": {"id": "1", "class": {"@ref": {"id": "cats", "class": {"@ref": {"id": "classes"}}}}}}}"#,
+        );
+
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        let mut data = Object::default();
+        data.insert("name", "Musti");
+
+        let reference = client
+            .create_returning_ref(Ref::class("cats"), data)
+            .unwrap();
+
+        assert_eq!("classes/cats/1", reference.path());
+
+        let payload: serde_json::Value =
+            serde_json::from_str(requests.lock().unwrap()[0].body()).unwrap();
+
+        assert_eq!(json!(["ref"]), payload["select"]);
+        assert!(payload["from"]["create"].is_object());
+    }
+
+    #[test]
+    fn test_create_returning_ref_eval() {
+        with_class(|client, class_name| {
+            let mut data = Object::default();
+            data.insert("name", "Musti");
+
+            let reference = client
+                .create_returning_ref(Ref::class(class_name), data)
+                .unwrap();
+
+            let response = client.query(Get::instance(reference.clone())).unwrap();
+
+            assert_eq!(Some(&reference), response.resource["ref"].as_reference());
+            assert_eq!(Some("Musti"), response.resource["data"]["name"].as_str());
+        })
+    }
+
+    #[test]
+    fn test_compare_and_update_eval() {
+        with_class(|client, class_name| {
+            let mut data = Object::default();
+            data.insert("age", 7);
+
+            let created = client
+                .query(Create::new(Ref::class(class_name), data))
+                .unwrap();
+
+            let reference = created.resource["ref"].as_reference().unwrap().clone();
+            let read_ts = created.resource["ts"].as_u64().unwrap();
+
+            let mut data = Object::default();
+            data.insert("age", 8);
+
+            let updated = client
+                .compare_and_update(reference.clone(), read_ts, data)
+                .unwrap();
+
+            assert_eq!(Some(8), updated.resource["data"]["age"].as_u64());
+
+            let mut data = Object::default();
+            data.insert("age", 9);
+
+            let error = client
+                .compare_and_update(reference, read_ts, data)
+                .unwrap_err();
+
+            assert_eq!(Some("conflict"), error.abort_message());
+        });
+    }
+
+    #[test]
+    fn test_project_as() {
+        let transport = MockTransport::new(200, r#"{"resource": {"name": "Musti", "age": 7}}"#);
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        let mut projection = Projection::new(Ref::instance("musti"));
+        projection.field("name", vec!["data", "name"]);
+        projection.field("age", vec!["data", "age"]);
+
+        let cat: Cat = client.project_as(projection).unwrap();
+
+        assert_eq!(
+            Cat {
+                name: String::from("Musti"),
+                age: 7
+            },
+            cat
+        );
+
+        let payload: serde_json::Value =
+            serde_json::from_str(requests.lock().unwrap()[0].body()).unwrap();
+
+        assert_eq!(
+            json!({"get": {"@ref": {"id": "musti"}}}),
+            payload["let"]["_v_doc"]
+        );
+    }
+
+    #[test]
+    fn test_conversion_error_names_field() {
+        let transport =
+            MockTransport::new(200, r#"{"resource": {"name": "Musti", "age": "seven"}}"#);
+        let client = mock_client(transport, |_| ());
+
+        let mut projection = Projection::new(Ref::instance("musti"));
+        projection.field("name", vec!["data", "name"]);
+        projection.field("age", vec!["data", "age"]);
+
+        match client.project_as::<Cat>(projection) {
+            Err(Error::ConversionError(message)) => {
+                assert!(message.ends_with("at `age`"), "{}", message);
+                assert!(message.contains("expected u64"), "{}", message);
+            }
+            result => panic!("Expected a conversion error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_query_batch_as() {
+        let transport = MockTransport::new(
+            200,
+            r#"{"resource": [{"name": "Musti", "age": 7}, {"name": "Naukio"}, {"@obj": {"name": "Pelle", "age": 2}}]}"#,
+        );
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        let results: Vec<crate::Result<Cat>> = client
+            .query_batch_as(vec![
+                Get::instance(Ref::instance("1")),
+                Get::instance(Ref::instance("2")),
+                Get::instance(Ref::instance("3")),
+            ])
+            .unwrap();
+
+        assert_eq!(3, results.len());
+
+        assert_eq!(
+            &Cat {
+                name: String::from("Musti"),
+                age: 7
+            },
+            results[0].as_ref().unwrap()
+        );
+
+        assert!(results[1].is_err());
+
+        assert_eq!(
+            &Cat {
+                name: String::from("Pelle"),
+                age: 2
+            },
+            results[2].as_ref().unwrap()
+        );
+
+        let payload: serde_json::Value =
+            serde_json::from_str(requests.lock().unwrap()[0].body()).unwrap();
+
+        assert_eq!(3, payload.as_array().unwrap().len());
+    }
+
+    #[test]
+    fn test_query_batch_as_simple_output() {
+        let transport = MockTransport::new(
+            200,
+            r#"{"resource": [{"@obj": {"@name": "Musti"}}, {"@ts": "2015-02-20T06:30:00Z"}]}"#,
+        );
+        let client = mock_client(transport, |builder| {
+            builder.tagged_output(false);
+        });
+
+        let results: Vec<crate::Result<serde_json::Value>> = client
+            .query_batch_as(vec![Expr::from("Musti"), Expr::from("born")])
+            .unwrap();
+
+        assert_eq!(&json!({"@name": "Musti"}), results[0].as_ref().unwrap());
+        assert_eq!(&json!("2015-02-20T06:30:00Z"), results[1].as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_query_batch_as_error() {
+        let transport = MockTransport::new(
+            400,
+            r#"{"errors": [{"position": [1], "code": "instance not found", "description": "Instance not found."}]}"#,
+        );
+        let client = mock_client(transport, |_| ());
+
+        match client.query_batch_as::<Cat, _>(vec![Get::instance(Ref::instance("1"))]) {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!("instance not found", errors.errors[0].code)
+            }
+            res => panic!("Expected a bad request, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_abort_message() {
+        let transport = MockTransport::new(
+            400,
+            r#"{"errors": [{"position": ["map", "expr"], "code": "transaction aborted", "description": "too big"}]}"#,
+        );
+        let client = mock_client(transport, |_| ());
+
+        let error = client.query(Abort::new("too big")).unwrap_err();
+        assert_eq!(Some("too big"), error.abort_message());
+
+        assert_eq!(None, Error::Unauthorized.abort_message());
+    }
+
+    #[test]
+    fn test_schema_version() {
+        let transport = MockTransport::new(200, r#"{"resource": null}"#)
+            .header("x-schema-version", "1565018361852000");
+
+        let changes = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = changes.clone();
+
+        let client = mock_client(transport, move |builder| {
+            builder.on_schema_change(move |_, new| {
+                recorded.lock().unwrap().push(String::from(new));
+            });
+        });
+
+        let response = client.query("meow").unwrap();
+        assert_eq!(Some("1565018361852000"), response.schema_version());
+
+        client.query("meow").unwrap();
+        assert_eq!(vec!["1565018361852000"], *changes.lock().unwrap());
+    }
+
+    #[test]
+    fn test_query_detailed() {
+        let transport = MockTransport::new(200, r#"{"resource": "meow"}"#)
+            .header("x-txn-time", "1565018361852000")
+            .header("x-read-ops", "1");
+
+        let client = mock_client(transport, |_| ());
+        let (response, status, headers) = client.query_detailed("meow").unwrap();
+
+        assert_eq!(Some("meow"), response.resource.as_str());
+        assert_eq!(http::StatusCode::OK, status);
+        assert_eq!("1565018361852000", headers["x-txn-time"]);
+        assert_eq!("1", headers["x-read-ops"]);
+    }
+
+    #[test]
+    fn test_driver_header() {
+        let transport = MockTransport::new(200, r#"{"resource": null}"#);
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        client.query("meow").unwrap();
+
+        let driver = format!("faunadb-rust/{}", env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            driver.as_str(),
+            requests.lock().unwrap()[0].headers()["x-fauna-driver"]
+        );
+
+        let transport = MockTransport::new(200, r#"{"resource": null}"#);
+        let requests = transport.requests();
+        let client = mock_client(transport, |builder| {
+            builder.application_name("cat-tracker");
+        });
+
+        client.query("meow").unwrap();
+
+        assert_eq!(
+            format!("{} cat-tracker", driver).as_str(),
+            requests.lock().unwrap()[0].headers()["x-fauna-driver"]
+        );
+
+        let mut builder = Client::builder("secret");
+        builder.application_name("cat\ntracker");
+
+        match builder.build() {
+            Err(Error::ConfigurationError(_)) => (),
+            Err(e) => panic!("Expected a configuration error, got {:?}", e),
+            Ok(_) => panic!("Expected a configuration error"),
+        }
+    }
+
+    #[test]
+    fn test_query_all() {
+        let transport = MockTransport::new(200, r#"{"resource": ["Musti", "Naukio"]}"#)
+            .header("x-compute-ops", "2");
+
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        let queries = vec!["Musti", "Naukio"].into_iter().map(|name| {
+            let mut data = Object::default();
+            data.insert("name", name);
+
+            Create::new(Ref::class("cats"), data)
+        });
+
+        let responses = client.query_all(queries).unwrap();
+
+        let names: Vec<_> = responses
+            .iter()
+            .map(|response| response.resource.as_str())
+            .collect();
+
+        assert_eq!(vec![Some("Musti"), Some("Naukio")], names);
+        assert_eq!(Some(2), responses[1].metrics().compute_ops);
+
+        let payload: serde_json::Value =
+            serde_json::from_str(requests.lock().unwrap()[0].body()).unwrap();
+
+        let expected = json!([
+            {
+                "create": {"@ref": {"class": {"@ref": {"id": "classes"}}, "id": "cats"}},
+                "params": {"object": {"data": {"object": {"name": "Musti"}}}}
+            },
+            {
+                "create": {"@ref": {"class": {"@ref": {"id": "classes"}}, "id": "cats"}},
+                "params": {"object": {"data": {"object": {"name": "Naukio"}}}}
+            }
+        ]);
+
+        assert_eq!(expected, payload);
+
+        let client = mock_client(MockTransport::new(200, r#"{"resource": 1}"#), |_| ());
+
+        match client.query_all(vec!["meow"]) {
+            Err(Error::ResponseDataFailure(_)) => (),
+            result => panic!("Expected a response data failure, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_redact_logs() {
+        let payload =
+            serde_json::to_string(&Expr::from(Login::new(Ref::instance("1"), "hunter2"))).unwrap();
+
+        let client = Client::builder("secret").build().unwrap();
+        assert!(!client.loggable(&payload).contains("hunter2"));
+
+        let mut builder = Client::builder("secret");
+        builder.redact_logs(false);
+
+        let client = builder.build().unwrap();
+        assert!(client.loggable(&payload).contains("hunter2"));
+    }
+
+    #[test]
+    fn test_last_seen_txn() {
+        let transport = MockTransport::new(200, r#"{"resource": null}"#)
+            .header("x-txn-time", "1565018361852000");
+
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        client.query("meow").unwrap();
+        client.query("meow").unwrap();
+
+        let requests = requests.lock().unwrap();
+
+        assert!(!requests[0].headers().contains_key("x-last-seen-txn"));
+        assert_eq!("1565018361852000", requests[1].headers()["x-last-seen-txn"]);
+    }
+
+    #[test]
+    fn test_query_with_format() {
+        let transport = MockTransport::new(
+            200,
+            r#"{"resource": {"ref": {"@ref": {"id": "1", "class": {"@ref": {"id": "cats", "class": {"@ref": {"id": "classes"}}}}}}}}"#,
+        );
+
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        let simple = client
+            .query_with_format("meow", OutputFormat::Simple)
+            .unwrap();
+
+        let tagged = client
+            .query_with_format("meow", OutputFormat::Tagged)
+            .unwrap();
+
+        assert_eq!(
+            Some("1"),
+            simple.resource["ref"]["id"].as_str(),
+            "the simple format drops the annotations"
+        );
+
+        assert!(tagged.resource["ref"].is_reference());
+
+        let requests = requests.lock().unwrap();
+
+        assert!(requests
+            .iter()
+            .all(|r| !r.headers().contains_key("x-format")));
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let transport = MockTransport::new(503, "Service Unavailable")
+            .then("Service Unavailable")
+            .then_status(200, r#"{"resource": "meow"}"#);
+
+        let requests = transport.requests();
+        let client = mock_client(transport, |builder| {
+            builder.retry_policy(RetryPolicy::new(2, std::time::Duration::from_millis(1)));
+        });
+
+        let response = client.query("meow").unwrap();
+
+        assert_eq!(Some("meow"), response.resource.as_str());
+        assert_eq!(3, requests.lock().unwrap().len());
+        assert_eq!(r#""meow""#, requests.lock().unwrap()[2].body());
+    }
+
+    #[test]
+    fn test_gateway_timeout_not_retried_for_writes() {
+        let transport =
+            MockTransport::new(504, "Gateway Timeout").then_status(200, r#"{"resource": "meow"}"#);
+
+        let requests = transport.requests();
+        let client = mock_client(transport, |builder| {
+            builder.retry_policy(RetryPolicy::new(2, std::time::Duration::from_millis(1)));
+        });
+
+        let mut data = Object::default();
+        data.insert("name", "Musti");
+
+        match client.query(Create::new(Ref::class("cats"), data)) {
+            Err(Error::DatabaseError(body)) => assert_eq!("Gateway Timeout", body),
+            result => panic!("Expected a database error, got {:?}", result),
+        }
+
+        assert_eq!(1, requests.lock().unwrap().len());
+    }
+
+    #[test]
+    fn test_gateway_timeout_retried_for_idempotent() {
+        let transport = MockTransport::new(504, "Gateway Timeout")
+            .then_status(502, "Bad Gateway")
+            .then_status(200, r#"{"resource": "meow"}"#);
+
+        let requests = transport.requests();
+        let client = mock_client(transport, |builder| {
+            builder.retry_policy(RetryPolicy::new(2, std::time::Duration::from_millis(1)));
+        });
+
+        let response = client.query_idempotent("meow").unwrap();
+
+        assert_eq!(Some("meow"), response.resource.as_str());
+        assert_eq!(3, requests.lock().unwrap().len());
+    }
+
+    #[test]
+    fn test_retries_exhausted() {
+        let transport = MockTransport::new(429, "Too Many Requests");
+        let requests = transport.requests();
+        let client = mock_client(transport, |builder| {
+            builder.retry_policy(RetryPolicy::new(1, std::time::Duration::from_millis(1)));
+        });
+
+        match client.query("meow") {
+            Err(Error::DatabaseError(body)) => assert_eq!("Too Many Requests", body),
+            result => panic!("Expected a database error, got {:?}", result),
+        }
+
+        assert_eq!(2, requests.lock().unwrap().len());
+
+        let transport = MockTransport::new(503, "Service Unavailable");
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        assert!(client.query("meow").is_err());
+        assert_eq!(1, requests.lock().unwrap().len());
+    }
+
+    #[test]
+    fn test_api_version_header() {
+        let transport = MockTransport::new(200, r#"{"resource": null}"#);
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        client.query("meow").unwrap();
+
+        assert_eq!(
+            "2.1",
+            requests.lock().unwrap()[0].headers()["x-faunadb-api-version"]
+        );
+
+        let transport = MockTransport::new(200, r#"{"resource": null}"#);
+        let requests = transport.requests();
+        let client = mock_client(transport, |builder| {
+            builder.api_version("2.7");
+        });
+
+        client.query("meow").unwrap();
+
+        assert_eq!(
+            "2.7",
+            requests.lock().unwrap()[0].headers()["x-faunadb-api-version"]
+        );
+    }
+
+    #[test]
+    fn test_response_metrics() {
+        let transport = MockTransport::new(200, r#"{"resource": null}"#)
+            .header("x-compute-ops", "2")
+            .header("x-query-time", "40");
+
+        let client = mock_client(transport, |_| ());
+        let response = client.query("meow").unwrap();
+
+        assert_eq!(Some(2), response.metrics().compute_ops);
+        assert_eq!(
+            Some(std::time::Duration::from_millis(40)),
+            response.metrics().query_time
+        );
+    }
+
+    #[test]
+    fn test_unexpected_response_body() {
+        let cases = [
+            (r#"{"unexpected": 1}"#, "missing field `resource`"),
+            ("meow", "expected value at line 1 column 1"),
+        ];
+
+        for (body, reason) in cases.iter() {
+            let client = mock_client(MockTransport::new(200, *body), |_| ());
+
+            match client.query("meow") {
+                Err(Error::ResponseDataFailure(message)) => {
+                    assert!(message.contains(reason), "{}", message)
+                }
+                result => panic!("Expected an unexpected response, got {:?}", result),
+            }
+        }
+
+        let client = mock_client(MockTransport::new(400, "meow"), |_| ());
+
+        match client.query("meow") {
+            Err(Error::DatabaseError(body)) => assert_eq!("meow", body),
+            result => panic!("Expected a database error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_server_version() {
+        let transport = MockTransport::new(200, r#"{"resource": null}"#)
+            .header("x-faunadb-build", "2.7.0-0c4e5b4");
+
+        let client = mock_client(transport, |_| ());
+        let response = client.query("meow").unwrap();
+
+        assert_eq!(Some("2.7.0-0c4e5b4"), response.server_build());
+        assert_eq!(Some(String::from("2.7.0-0c4e5b4")), client.server_version());
+
+        let client = mock_client(MockTransport::new(200, r#"{"resource": null}"#), |_| ());
+        let response = client.query("meow").unwrap();
+
+        assert_eq!(None, response.server_build());
+        assert_eq!(None, client.server_version());
+    }
+
+    #[test]
+    fn test_no_schema_version() {
+        let client = mock_client(MockTransport::new(200, r#"{"resource": null}"#), |_| ());
+        let response = client.query("meow").unwrap();
+
+        assert_eq!(None, response.schema_version());
+    }
+
+    #[test]
+    fn test_query_within_budget() {
+        let transport = MockTransport::new(200, r#"{"resource": "meow"}"#)
+            .header("x-read-ops", "5")
+            .header("x-write-ops", "1");
+
+        let client = mock_client(transport, |builder| {
+            builder.max_read_ops(5);
+            builder.max_write_ops(1);
+        });
+
+        let response = client.query("meow").unwrap();
+
+        assert_eq!(Value::from("meow"), response.resource);
+    }
+
+    #[test]
+    fn test_query_over_read_budget() {
+        let transport = MockTransport::new(200, r#"{"resource": []}"#)
+            .header("x-read-ops", "100000")
+            .header("x-write-ops", "0");
+
+        let client = mock_client(transport, |builder| {
+            builder.max_read_ops(100);
+        });
+
+        match client.query(Paginate::new(Classes::all())) {
+            Err(Error::BudgetExceeded { kind, used, budget }) => {
+                assert_eq!("read", kind);
+                assert_eq!(100000, used);
+                assert_eq!(100, budget);
+            }
+            res => panic!("Expected a budget error, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_query_over_write_budget() {
+        let transport = MockTransport::new(200, r#"{"resource": null}"#)
+            .header("x-read-ops", "0")
+            .header("x-write-ops", "50");
+
+        let client = mock_client(transport, |builder| {
+            builder.max_write_ops(10);
+        });
+
+        match client.query("meow") {
+            Err(Error::BudgetExceeded { kind, .. }) => assert_eq!("write", kind),
+            res => panic!("Expected a budget error, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_failed_query_reports_the_query_error() {
+        let transport = MockTransport::new(401, "").header("x-read-ops", "1000");
+
+        let client = mock_client(transport, |builder| {
+            builder.max_read_ops(1);
+        });
+
+        match client.query("meow") {
+            Err(Error::Unauthorized) => (),
+            res => panic!("Expected an unauthorized error, got {:?}", res),
+        }
+    }
+
+    struct StreamingTransport {
+        chunks_sent: Arc<AtomicUsize>,
+    }
+
+    impl Transport for StreamingTransport {
+        fn call(&self, _: http::Request<hyper::Body>) -> TransportFuture {
+            let chunks_sent = self.chunks_sent.clone();
+
+            let chunks = futures::stream::repeat::<_, std::io::Error>(vec![b' '; 1024])
+                .take(10_000)
+                .inspect(move |_| {
+                    chunks_sent.fetch_add(1, Ordering::SeqCst);
+                });
+
+            let response = http::Response::new(hyper::Body::wrap_stream(chunks));
+
+            Box::new(futures::future::ok(response))
+        }
+    }
+
+    #[test]
+    fn test_max_response_bytes() {
+        let client = mock_client(MockTransport::new(200, TAGGED_RESPONSE), |builder| {
+            builder.max_response_bytes(TAGGED_RESPONSE.len());
+        });
+
+        assert!(client.query("meow").is_ok());
+
+        let client = mock_client(MockTransport::new(200, TAGGED_RESPONSE), |builder| {
+            builder.max_response_bytes(TAGGED_RESPONSE.len() - 1);
+        });
+
+        match client.query("meow") {
+            Err(Error::ResponseTooLarge(limit)) => assert_eq!(TAGGED_RESPONSE.len() - 1, limit),
+            res => panic!("Expected a too large response, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_max_response_bytes_stops_reading() {
+        let chunks_sent = Arc::new(AtomicUsize::new(0));
+
+        let mut builder = Client::builder("secret");
+        builder.max_response_bytes(64 * 1024);
+        builder.transport(StreamingTransport {
+            chunks_sent: chunks_sent.clone(),
+        });
+
+        let client = builder.build_sync().unwrap();
+
+        match client.query("meow") {
+            Err(Error::ResponseTooLarge(_)) => (),
+            res => panic!("Expected a too large response, got {:?}", res),
+        }
+
+        assert!(chunks_sent.load(Ordering::SeqCst) < 100);
+    }
+
+    #[test]
+    fn test_max_response_bytes_with_record() {
+        let chunks_sent = Arc::new(AtomicUsize::new(0));
+        let recorded = Arc::new(AtomicUsize::new(0));
+        let sink = recorded.clone();
+
+        let mut builder = Client::builder("secret");
+        builder.max_response_bytes(64 * 1024);
+        builder.record(move |_, _| {
+            sink.fetch_add(1, Ordering::SeqCst);
+        });
+        builder.transport(StreamingTransport {
+            chunks_sent: chunks_sent.clone(),
+        });
+
+        let client = builder.build_sync().unwrap();
+
+        match client.query("meow") {
+            Err(Error::ResponseTooLarge(_)) => (),
+            res => panic!("Expected a too large response, got {:?}", res),
+        }
+
+        assert!(chunks_sent.load(Ordering::SeqCst) < 100);
+        assert_eq!(0, recorded.load(Ordering::SeqCst));
+    }
+
+    struct ContentLengthTransport;
+
+    impl Transport for ContentLengthTransport {
+        fn call(&self, _: http::Request<hyper::Body>) -> TransportFuture {
+            let response = http::Response::builder()
+                .header(http::header::CONTENT_LENGTH, "1000000")
+                .body(hyper::Body::from(TAGGED_RESPONSE))
+                .unwrap();
+
+            Box::new(futures::future::ok(response))
+        }
+    }
+
+    #[test]
+    fn test_max_response_bytes_checks_content_length() {
+        let mut builder = Client::builder("secret");
+        builder.max_response_bytes(64 * 1024);
+        builder.transport(ContentLengthTransport);
+
+        let client = builder.build_sync().unwrap();
+
+        match client.query("meow") {
+            Err(Error::ResponseTooLarge(limit)) => assert_eq!(64 * 1024, limit),
+            res => panic!("Expected a too large response, got {:?}", res),
+        }
+    }
+
+    struct SlowTransport {
+        responded: Arc<AtomicBool>,
+    }
+
+    impl Transport for SlowTransport {
+        fn call(&self, _: http::Request<hyper::Body>) -> TransportFuture {
+            let responded = self.responded.clone();
+            let delay = std::time::Instant::now() + std::time::Duration::from_millis(100);
+
+            let response = tokio_timer::Delay::new(delay)
+                .map_err(|_| Error::TimeoutError)
+                .map(move |_| {
+                    responded.store(true, Ordering::SeqCst);
+                    http::Response::new(hyper::Body::from(TAGGED_RESPONSE))
+                });
+
+            Box::new(response)
+        }
+    }
+
+    #[test]
+    fn test_query_with_timeout() {
+        let transport = MockTransport::new(200, r#"{"resource": "meow"}"#);
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        let response = client
+            .query_with_timeout("meow", std::time::Duration::from_secs(120))
+            .unwrap();
+
+        assert_eq!(Some("meow"), response.resource.as_str());
+        assert_eq!(
+            "120000",
+            requests.lock().unwrap()[0].headers()["x-query-timeout"]
+        );
+
+        client.query("meow").unwrap();
+        assert!(requests.lock().unwrap()[1]
+            .headers()
+            .get("x-query-timeout")
+            .is_none());
+
+        let mut builder = Client::builder("secret");
+        builder.transport(SlowTransport {
+            responded: Arc::new(AtomicBool::new(false)),
+        });
+
+        let client = builder.build_sync().unwrap();
+
+        match client.query_with_timeout("meow", std::time::Duration::from_millis(10)) {
+            Err(Error::TimeoutError) => (),
+            res => panic!("Expected a timeout, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_query_timed() {
+        let mut builder = Client::builder("secret");
+        builder.transport(SlowTransport {
+            responded: Arc::new(AtomicBool::new(false)),
+        });
+
+        let client = builder.build_sync().unwrap();
+        let (response, elapsed) = client.query_timed("meow").unwrap();
+
+        assert_eq!(Some(1), response.resource["ts"].as_u64());
+        assert!(elapsed >= std::time::Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_shutdown_waits_for_requests() {
+        let responded = Arc::new(AtomicBool::new(false));
+
+        let mut builder = Client::builder("secret");
+        builder.transport(SlowTransport {
+            responded: responded.clone(),
+        });
+
+        let client = builder.build().unwrap();
+        let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+        runtime.spawn(client.query("meow").map(|_| ()).map_err(|_| ()));
+        runtime.block_on(client.shutdown()).unwrap();
+
+        assert!(responded.load(Ordering::SeqCst));
+    }
+}
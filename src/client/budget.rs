@@ -0,0 +1,97 @@
+use crate::error::Error;
+use http::header::HeaderMap;
+
+/// The maximum number of operations a single query is allowed to use.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Budget {
+    pub(crate) read_ops: Option<u64>,
+    pub(crate) write_ops: Option<u64>,
+}
+
+impl Budget {
+    /// Checks the ops reported in the response headers against the budget.
+    pub(crate) fn check(&self, headers: &HeaderMap) -> crate::Result<()> {
+        Self::check_ops(headers, "x-read-ops", "read", self.read_ops)?;
+        Self::check_ops(headers, "x-write-ops", "write", self.write_ops)?;
+
+        Ok(())
+    }
+
+    fn check_ops(
+        headers: &HeaderMap,
+        header: &str,
+        kind: &'static str,
+        budget: Option<u64>,
+    ) -> crate::Result<()> {
+        let budget = match budget {
+            Some(budget) => budget,
+            None => return Ok(()),
+        };
+
+        let used = headers
+            .get(header)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+
+        match used {
+            Some(used) if used > budget => Err(Error::BudgetExceeded { kind, used, budget }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::HeaderValue;
+
+    fn headers(read_ops: &'static str, write_ops: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-read-ops", HeaderValue::from_static(read_ops));
+        headers.insert("x-write-ops", HeaderValue::from_static(write_ops));
+        headers
+    }
+
+    #[test]
+    fn test_no_budget() {
+        let budget = Budget::default();
+        assert!(budget.check(&headers("1000000", "1000000")).is_ok());
+    }
+
+    #[test]
+    fn test_within_budget() {
+        let budget = Budget {
+            read_ops: Some(10),
+            write_ops: Some(1),
+        };
+
+        assert!(budget.check(&headers("10", "1")).is_ok());
+    }
+
+    #[test]
+    fn test_read_budget_exceeded() {
+        let budget = Budget {
+            read_ops: Some(10),
+            write_ops: None,
+        };
+
+        match budget.check(&headers("11", "1000")) {
+            Err(Error::BudgetExceeded { kind, used, budget }) => {
+                assert_eq!("read", kind);
+                assert_eq!(11, used);
+                assert_eq!(10, budget);
+            }
+            res => panic!("Expected a budget error, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_missing_headers() {
+        let budget = Budget {
+            read_ops: Some(0),
+            write_ops: Some(0),
+        };
+
+        assert!(budget.check(&HeaderMap::new()).is_ok());
+    }
+}
@@ -0,0 +1,253 @@
+//! Deserializing JSON while keeping track of the field being deserialized, so
+//! a failed conversion can tell where in the value it failed.
+
+use serde::de::{
+    self, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde_json::{Error, Value};
+use std::{cell::RefCell, fmt};
+
+/// Deserializes `T` from the JSON. The error has the path of the field that
+/// failed, e.g. `data.friends[1]`, if it failed below the top level.
+pub(crate) fn from_json<T>(json: &Value) -> Result<T, (Error, Option<String>)>
+where
+    T: de::DeserializeOwned,
+{
+    let failed_at = RefCell::new(None);
+
+    let deserializer = Tracked {
+        value: json,
+        segment: Segment::Root,
+        failed_at: &failed_at,
+    };
+
+    T::deserialize(deserializer).map_err(|e| (e, failed_at.into_inner()))
+}
+
+#[derive(Clone, Copy)]
+enum Segment<'p> {
+    Root,
+    Field(&'p str, &'p Segment<'p>),
+    Index(usize, &'p Segment<'p>),
+}
+
+impl<'p> fmt::Display for Segment<'p> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Segment::Root => Ok(()),
+            Segment::Field(name, Segment::Root) => write!(f, "{}", name),
+            Segment::Field(name, parent) => write!(f, "{}.{}", parent, name),
+            Segment::Index(i, parent) => write!(f, "{}[{}]", parent, i),
+        }
+    }
+}
+
+struct Tracked<'de, 'p> {
+    value: &'de Value,
+    segment: Segment<'p>,
+    failed_at: &'p RefCell<Option<String>>,
+}
+
+impl<'de, 'p> Tracked<'de, 'p> {
+    /// Remembers the deepest field that failed, which is the first one to
+    /// see the error.
+    fn track<T>(&self, result: Result<T, Error>) -> Result<T, Error> {
+        if result.is_err() {
+            let mut failed_at = self.failed_at.borrow_mut();
+
+            if failed_at.is_none() && !matches!(self.segment, Segment::Root) {
+                *failed_at = Some(self.segment.to_string());
+            }
+        }
+
+        result
+    }
+}
+
+impl<'de, 'p> Deserializer<'de> for Tracked<'de, 'p> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = match self.value {
+            Value::Object(map) => visitor.visit_map(TrackedMap {
+                iter: map.iter(),
+                value: None,
+                segment: &self.segment,
+                failed_at: self.failed_at,
+            }),
+            Value::Array(values) => visitor.visit_seq(TrackedSeq {
+                iter: values.iter().enumerate(),
+                segment: &self.segment,
+                failed_at: self.failed_at,
+            }),
+            value => value.deserialize_any(visitor),
+        };
+
+        self.track(result)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = self.value.deserialize_enum(name, variants, visitor);
+        self.track(result)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct TrackedMap<'de, 'p> {
+    iter: serde_json::map::Iter<'de>,
+    value: Option<(&'de str, &'de Value)>,
+    segment: &'p Segment<'p>,
+    failed_at: &'p RefCell<Option<String>>,
+}
+
+impl<'de, 'p> MapAccess<'de> for TrackedMap<'de, 'p> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some((key.as_str(), value));
+                seed.deserialize(key.as_str().into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value is missing"))?;
+
+        let segment = Segment::Field(key, self.segment);
+
+        seed.deserialize(Tracked {
+            value,
+            segment,
+            failed_at: self.failed_at,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct TrackedSeq<'de, 'p> {
+    iter: std::iter::Enumerate<std::slice::Iter<'de, Value>>,
+    segment: &'p Segment<'p>,
+    failed_at: &'p RefCell<Option<String>>,
+}
+
+impl<'de, 'p> SeqAccess<'de> for TrackedSeq<'de, 'p> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((i, value)) => {
+                let segment = Segment::Index(i, self.segment);
+
+                seed.deserialize(Tracked {
+                    value,
+                    segment,
+                    failed_at: self.failed_at,
+                })
+                .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Deserialize, Debug)]
+    struct Cat {
+        #[allow(dead_code)]
+        name: String,
+        #[allow(dead_code)]
+        friends: Vec<Friend>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Friend {
+        #[allow(dead_code)]
+        age: u64,
+    }
+
+    #[test]
+    fn test_nested_field_path() {
+        let json = json!({"name": "Musti", "friends": [{"age": 3}, {"age": "old"}]});
+        let (error, path) = from_json::<Cat>(&json).unwrap_err();
+
+        assert_eq!(Some("friends[1].age"), path.as_deref());
+        assert!(error.to_string().contains("expected u64"));
+    }
+
+    #[test]
+    fn test_missing_field_path() {
+        let json = json!({"name": "Musti", "friends": [{}]});
+        let (error, path) = from_json::<Cat>(&json).unwrap_err();
+
+        assert_eq!(Some("friends[0]"), path.as_deref());
+        assert!(error.to_string().contains("missing field `age`"));
+    }
+
+    #[test]
+    fn test_top_level_failure() {
+        let (_, path) = from_json::<Cat>(&json!(1)).unwrap_err();
+        assert_eq!(None, path);
+
+        let cat = from_json::<Cat>(&json!({"name": "Musti", "friends": []}));
+        assert!(cat.is_ok());
+    }
+}
@@ -0,0 +1,263 @@
+use super::{Client, FutureResponse};
+use crate::{
+    error::Error,
+    expr::{AnnotatedExpr, Expr, Object, SimpleExpr},
+};
+use http::header::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE};
+use hyper::{Body, StatusCode};
+use serde_json::{self, json};
+
+/// The path of the query endpoint for FQL v10 text queries.
+pub(crate) const FQL_PATH: &str = "/query/1";
+
+const FORMAT_HEADER: &str = "x-format";
+
+/// The timestamp annotation of FQL v10, called `@ts` in the older versions.
+const TIME_ANNOTATION: &str = "@time";
+
+const DATE_ANNOTATION: &str = "@date";
+
+/// Escapes objects with user keys starting with `@`.
+const OBJECT_ANNOTATION: &str = "@object";
+
+#[derive(Serialize, Debug)]
+struct FqlRequest<'a> {
+    query: &'a str,
+    arguments: serde_json::Value,
+}
+
+impl<'a> FqlRequest<'a> {
+    fn new(query: &'a str, args: Object) -> crate::Result<Self> {
+        let arguments = encode_object(&args)?;
+
+        Ok(Self { query, arguments })
+    }
+}
+
+/// Encodes an argument in the tagged format of FQL v10. Objects are sent as
+/// plain JSON objects, wrapped in `@object` if any of the keys starts with
+/// `@`, so they can't be confused with the annotations.
+fn encode(expr: &Expr) -> crate::Result<serde_json::Value> {
+    match expr {
+        Expr::Simple(SimpleExpr::Array(array)) => Ok(serde_json::Value::Array(
+            array.0.iter().map(encode).collect::<crate::Result<_>>()?,
+        )),
+        Expr::Simple(SimpleExpr::Object(object))
+        | Expr::Annotated(AnnotatedExpr::Object(object)) => encode_object(object),
+        Expr::Annotated(AnnotatedExpr::Timestamp(ts)) => Ok(json!({ TIME_ANNOTATION: ts })),
+        Expr::Annotated(AnnotatedExpr::Date(date)) => Ok(json!({ DATE_ANNOTATION: date })),
+        Expr::Simple(_)
+        | Expr::Annotated(AnnotatedExpr::Bytes(_))
+        | Expr::Annotated(AnnotatedExpr::Module(_)) => serde_json::to_value(expr)
+            .map_err(|_| Error::RequestDataFailure("Arguments could not be serialized")),
+        _ => Err(Error::RequestDataFailure(
+            "Only data and modules can be used as FQL v10 arguments",
+        )),
+    }
+}
+
+fn encode_object(object: &Object) -> crate::Result<serde_json::Value> {
+    let map = object
+        .0
+        .iter()
+        .map(|(k, v)| Ok((k.to_string(), encode(v)?)))
+        .collect::<crate::Result<serde_json::Map<_, _>>>()?;
+
+    if map.keys().any(|k| k.starts_with('@')) {
+        Ok(json!({ OBJECT_ANNOTATION: map }))
+    } else {
+        Ok(serde_json::Value::Object(map))
+    }
+}
+
+/// A response to an FQL v10 text query.
+///
+/// The `data` is in the simple JSON format, without any type annotations.
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct FqlResponse {
+    pub data: serde_json::Value,
+    #[serde(default)]
+    pub static_type: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub txn_ts: Option<i64>,
+}
+
+impl Client {
+    /// Send an FQL v10 query as text, with `args` bound as variables in the
+    /// query.
+    ///
+    /// The arguments should consist of strings, numbers, booleans, arrays and
+    /// objects, and collections and functions can be passed as a
+    /// [Module](../expr/struct.Module.html).
+    ///
+    /// ```no_run
+    /// # use faunadb::prelude::*;
+    /// let client = Client::builder("my_fauna_secret").build().unwrap();
+    ///
+    /// let mut args = Object::default();
+    /// args.insert("name", "Musti");
+    ///
+    /// client.query_fql("Cats.byName(name).first()", args);
+    /// ```
+    pub fn query_fql<'a>(&self, fql: &str, args: Object<'a>) -> FutureResponse<FqlResponse> {
+        let payload_json = match FqlRequest::new(fql, args).and_then(|request| {
+            serde_json::to_string(&request)
+                .map_err(|_| Error::RequestDataFailure("Query could not be serialized"))
+        }) {
+            Ok(payload_json) => payload_json,
+            Err(e) => return FutureResponse::err(e),
+        };
+
+        trace!("Querying FQL with: {:?}", self.loggable(&payload_json));
+
+        self.request(
+            self.build_fql_request(payload_json),
+            |status, _, body| match status {
+                s if s.is_success() => {
+                    serde_json::from_str(&body).map_err(super::unexpected_response)
+                }
+                StatusCode::UNAUTHORIZED => Err(Error::Unauthorized),
+                _ => Err(Error::DatabaseError(body)),
+            },
+        )
+    }
+
+    fn build_fql_request(&self, payload: String) -> hyper::Request<Body> {
+        let mut builder = hyper::Request::builder();
+
+        builder.uri(&self.fql_uri);
+        builder.method("POST");
+
+        builder.header(CONTENT_LENGTH, format!("{}", payload.len()).as_bytes());
+        builder.header(CONTENT_TYPE, "application/json");
+        builder.header(AUTHORIZATION, self.bearer.as_bytes());
+        builder.header(FORMAT_HEADER, "simple");
+        builder.header(super::DRIVER_HEADER, self.driver.clone());
+
+        builder.body(Body::from(payload)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, prelude::*};
+    use chrono::{offset::TimeZone, NaiveDate, Utc};
+    use futures::{Future, Stream};
+    use serde_json::{self, json};
+
+    #[test]
+    fn test_fql_request() {
+        let mut builder = Client::builder("secret");
+        builder.uri("http://localhost:8443");
+
+        let client = builder.build().unwrap();
+
+        let mut args = Object::default();
+        args.insert("name", "Musti");
+
+        let payload =
+            serde_json::to_string(&super::FqlRequest::new("Cats.byName(name)", args).unwrap())
+                .unwrap();
+
+        let request = client.build_fql_request(payload);
+
+        assert_eq!("http://localhost:8443/query/1", request.uri().to_string());
+        assert_eq!("Bearer secret", request.headers()["authorization"]);
+        assert_eq!("simple", request.headers()["x-format"]);
+
+        let body = request.into_body().concat2().wait().unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        let expected = json!({
+            "query": "Cats.byName(name)",
+            "arguments": {"name": "Musti"}
+        });
+
+        assert_eq!(expected, body);
+    }
+
+    #[test]
+    fn test_fql_request_timestamps() {
+        let mut args = Object::default();
+        args.insert("born", Utc.timestamp_opt(60, 0).unwrap());
+        args.insert(
+            "dates",
+            Array::from(vec![Utc.timestamp_opt(60, 0).unwrap()]),
+        );
+
+        let payload = serde_json::to_value(super::FqlRequest::new("born", args).unwrap()).unwrap();
+
+        let expected = json!({
+            "born": {"@time": "1970-01-01T00:01:00Z"},
+            "dates": [{"@time": "1970-01-01T00:01:00Z"}]
+        });
+
+        assert_eq!(expected, payload["arguments"]);
+    }
+
+    #[test]
+    fn test_fql_request_nested_objects() {
+        let mut args = Object::default();
+        args.insert_object("owner", |owner| {
+            owner
+                .insert("name", "Julius")
+                .insert_object("address", |address| {
+                    address.insert("city", "Berlin");
+                });
+        });
+        args.insert("born", NaiveDate::from_ymd_opt(2015, 2, 20).unwrap());
+
+        let payload = serde_json::to_value(super::FqlRequest::new("owner", args).unwrap()).unwrap();
+
+        let expected = json!({
+            "owner": {"name": "Julius", "address": {"city": "Berlin"}},
+            "born": {"@date": "2015-02-20"}
+        });
+
+        assert_eq!(expected, payload["arguments"]);
+    }
+
+    #[test]
+    fn test_fql_request_escapes_user_annotation_keys() {
+        let mut args = Object::default();
+        args.insert_object("cat", |cat| {
+            cat.insert("@ts", "yesterday");
+        });
+
+        let payload = serde_json::to_value(super::FqlRequest::new("cat", args).unwrap()).unwrap();
+
+        let expected = json!({
+            "cat": {"@object": {"@ts": "yesterday"}}
+        });
+
+        assert_eq!(expected, payload["arguments"]);
+    }
+
+    #[test]
+    fn test_fql_request_rejects_queries() {
+        let mut args = Object::default();
+        args.insert("cat", Get::instance(Ref::instance("musti")));
+
+        match super::FqlRequest::new("cat", args) {
+            Err(Error::RequestDataFailure(_)) => (),
+            _ => panic!("a query must not be sent as an argument"),
+        }
+    }
+
+    #[test]
+    fn test_query_fql_eval() {
+        let mut builder = Client::builder("secret");
+        builder.uri("http://localhost:8443");
+
+        let client = builder.build_sync().unwrap();
+
+        let mut args = Object::default();
+        args.insert("x", 2);
+
+        let response = client.query_fql("x + 1", args).unwrap();
+
+        assert_eq!(json!(3), response.data);
+    }
+}
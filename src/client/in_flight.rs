@@ -0,0 +1,64 @@
+use futures::{
+    task::{self, Task},
+    Async, Future, Poll,
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+/// Counts the requests in flight, waking up the tasks waiting for the count
+/// to drop to zero.
+#[derive(Default)]
+pub(crate) struct InFlight {
+    count: AtomicUsize,
+    waiting: Mutex<Vec<Task>>,
+}
+
+impl InFlight {
+    /// Marks a request started. The request is done when the guard is
+    /// dropped.
+    pub(crate) fn start(self: &Arc<Self>) -> InFlightGuard {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard(self.clone())
+    }
+
+    /// A future resolving when no requests are in flight.
+    pub(crate) fn drained(self: &Arc<Self>) -> Drained {
+        Drained(self.clone())
+    }
+}
+
+pub(crate) struct InFlightGuard(Arc<InFlight>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            for task in self.0.waiting.lock().unwrap().drain(..) {
+                task.notify();
+            }
+        }
+    }
+}
+
+pub(crate) struct Drained(Arc<InFlight>);
+
+impl Future for Drained {
+    type Item = ();
+    type Error = crate::error::Error;
+
+    fn poll(&mut self) -> Poll<(), Self::Error> {
+        if self.0.count.load(Ordering::SeqCst) == 0 {
+            return Ok(Async::Ready(()));
+        }
+
+        self.0.waiting.lock().unwrap().push(task::current());
+
+        // The last request might have finished before we registered.
+        if self.0.count.load(Ordering::SeqCst) == 0 {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use http::header::HeaderMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The header Fauna reports the transaction time of the query in.
+pub(crate) const TXN_TIME_HEADER: &str = "x-txn-time";
+
+/// The header telling Fauna the latest transaction the client has seen, so
+/// the following reads never see older data.
+pub(crate) const LAST_SEEN_TXN_HEADER: &str = "x-last-seen-txn";
+
+/// Tracks the highest transaction time seen in the responses.
+#[derive(Default)]
+pub(crate) struct LastTxn {
+    last_seen: AtomicU64,
+}
+
+impl LastTxn {
+    /// Reads the transaction time from the headers, keeping the highest one.
+    pub(crate) fn observe(&self, headers: &HeaderMap) {
+        let txn_time = headers
+            .get(TXN_TIME_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
+
+        if let Some(txn_time) = txn_time {
+            self.last_seen.fetch_max(txn_time, Ordering::SeqCst);
+        }
+    }
+
+    /// The highest transaction time seen, `None` before the first.
+    pub(crate) fn get(&self) -> Option<u64> {
+        match self.last_seen.load(Ordering::SeqCst) {
+            0 => None,
+            txn_time => Some(txn_time),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::HeaderValue;
+
+    #[test]
+    fn test_keeps_highest_txn_time() {
+        let last_txn = LastTxn::default();
+        assert_eq!(None, last_txn.get());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            TXN_TIME_HEADER,
+            HeaderValue::from_static("1565018361852000"),
+        );
+        last_txn.observe(&headers);
+
+        headers.insert(
+            TXN_TIME_HEADER,
+            HeaderValue::from_static("1565018361000000"),
+        );
+        last_txn.observe(&headers);
+        last_txn.observe(&HeaderMap::new());
+
+        assert_eq!(Some(1565018361852000), last_txn.get());
+    }
+}
@@ -0,0 +1,187 @@
+use super::transport::{read_body, Transport, TransportFuture};
+use crate::error::Error;
+use futures::{future, Future};
+use hyper::{Body, Request, Response};
+use std::{collections::HashMap, sync::Arc};
+
+pub(crate) type RecordSink = Box<dyn Fn(&str, &str) + Send + Sync>;
+
+/// Passes the requests to the inner transport, calling the sink with the
+/// payload and the body of every successful response. The response is read
+/// up to the `max_response_bytes` of the client.
+pub(crate) struct Recording {
+    inner: Arc<dyn Transport>,
+    sink: Arc<RecordSink>,
+    max_response_bytes: Option<usize>,
+}
+
+impl Recording {
+    pub(crate) fn new(
+        inner: Arc<dyn Transport>,
+        sink: RecordSink,
+        max_response_bytes: Option<usize>,
+    ) -> Self {
+        Self {
+            inner,
+            sink: Arc::new(sink),
+            max_response_bytes,
+        }
+    }
+}
+
+impl Transport for Recording {
+    fn call(&self, request: Request<Body>) -> TransportFuture {
+        let inner = self.inner.clone();
+        let sink = self.sink.clone();
+        let max_response_bytes = self.max_response_bytes;
+        let (parts, body) = request.into_parts();
+
+        let recording = read_body(&parts.headers, body, None).and_then(move |payload| {
+            let request = Request::from_parts(parts, Body::from(payload.clone()));
+
+            inner.call(request).and_then(move |response| {
+                let (parts, body) = response.into_parts();
+
+                read_body(&parts.headers, body, max_response_bytes).map(move |body| {
+                    if parts.status.is_success() {
+                        if let (Ok(payload), Ok(body)) =
+                            (std::str::from_utf8(&payload), std::str::from_utf8(&body))
+                        {
+                            sink(payload, body);
+                        }
+                    }
+
+                    Response::from_parts(parts, Body::from(body))
+                })
+            })
+        });
+
+        Box::new(recording)
+    }
+}
+
+/// A [Transport](trait.Transport.html) serving recorded responses, matched by
+/// the exact request payload. Pairs can be recorded from real traffic with
+/// [ClientBuilder::record](struct.ClientBuilder.html#method.record).
+///
+/// The responses are served with the status `200 OK`. A query with no
+/// recorded response fails with `Error::RequestDataFailure`.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// let mut replay = Replay::new();
+/// replay.insert("\"meow\"", r#"{"resource": "meow"}"#);
+///
+/// let mut builder = Client::builder("my_fauna_secret");
+/// builder.transport(replay);
+///
+/// let client = builder.build_sync().unwrap();
+/// let response = client.query("meow").unwrap();
+///
+/// assert_eq!(Some("meow"), response.resource.as_str());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Replay {
+    responses: HashMap<String, String>,
+}
+
+impl Replay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Respond with the `response` body to requests with the `payload`.
+    pub fn insert(&mut self, payload: impl Into<String>, response: impl Into<String>) -> &mut Self {
+        self.responses.insert(payload.into(), response.into());
+        self
+    }
+}
+
+impl Transport for Replay {
+    fn call(&self, request: Request<Body>) -> TransportFuture {
+        let responses = self.responses.clone();
+
+        let (parts, body) = request.into_parts();
+
+        let replaying = read_body(&parts.headers, body, None).and_then(move |payload| {
+            let response = String::from_utf8(payload)
+                .ok()
+                .and_then(|payload| responses.get(&payload).cloned());
+
+            match response {
+                Some(body) => future::ok(Response::new(Body::from(body))),
+                None => future::err(Error::RequestDataFailure(
+                    "No recorded response for the query",
+                )),
+            }
+        });
+
+        Box::new(replaying)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, prelude::*, test_utils::*};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_record_and_replay() {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let sink = recorded.clone();
+
+        let client = mock_client(
+            MockTransport::new(200, r#"{"resource": "meow"}"#),
+            move |builder| {
+                builder.record(move |payload, body| {
+                    sink.lock()
+                        .unwrap()
+                        .push((String::from(payload), String::from(body)));
+                });
+            },
+        );
+
+        let recorded_response = client
+            .query(Concat::new(Array::from(vec!["me", "ow"]), ""))
+            .unwrap();
+
+        let mut replay = Replay::new();
+        for (payload, body) in recorded.lock().unwrap().iter() {
+            replay.insert(payload.as_str(), body.as_str());
+        }
+
+        let mut builder = Client::builder("secret");
+        builder.transport(replay);
+        let client = builder.build_sync().unwrap();
+
+        let replayed_response = client
+            .query(Concat::new(Array::from(vec!["me", "ow"]), ""))
+            .unwrap();
+
+        assert_eq!(1, recorded.lock().unwrap().len());
+        assert_eq!(recorded_response.resource, replayed_response.resource);
+
+        match client.query(Concat::new(Array::from(vec!["wo", "em"]), "")) {
+            Err(Error::RequestDataFailure(_)) => (),
+            result => panic!("Expected a replay miss, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_record_skips_errors() {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let sink = recorded.clone();
+
+        let client = mock_client(
+            MockTransport::new(401, r#"{"errors": []}"#),
+            move |builder| {
+                builder.record(move |payload, _| {
+                    sink.lock().unwrap().push(String::from(payload));
+                });
+            },
+        );
+
+        assert!(client.query("meow").is_err());
+        assert!(recorded.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// The fields never written to the logs as is.
+const SENSITIVE_FIELDS: &[&str] = &["password", "secret", "credentials", "key_from_secret"];
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Masks the sensitive fields in a JSON payload for logging. A payload that
+/// is not JSON is returned unchanged, having no fields to mask.
+pub(crate) fn redact(payload: &str) -> Cow<'_, str> {
+    let mut json = match serde_json::from_str::<Value>(payload) {
+        Ok(json) => json,
+        Err(_) => return Cow::from(payload),
+    };
+
+    if mask(&mut json) {
+        Cow::from(json.to_string())
+    } else {
+        Cow::from(payload)
+    }
+}
+
+/// True, if anything was masked.
+fn mask(value: &mut Value) -> bool {
+    let mut masked = false;
+
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if SENSITIVE_FIELDS.contains(&key.as_str()) {
+                    *value = Value::from(REDACTED);
+                    masked = true;
+                } else {
+                    masked |= mask(value);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values.iter_mut() {
+                masked |= mask(value);
+            }
+        }
+        _ => (),
+    }
+
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use serde_json::{self, json};
+
+    #[test]
+    fn test_redact_login() {
+        let queries = Array::from(vec![
+            Expr::from(Login::new(Ref::instance("1"), "hunter2")),
+            Expr::from(Identify::new(Ref::instance("1"), "hunter2")),
+            Expr::from(KeyFromSecret::new("fnAAAhunter2")),
+        ]);
+
+        let payload = serde_json::to_string(&Expr::from(queries)).unwrap();
+
+        assert!(payload.contains("hunter2"));
+
+        let redacted = redact(&payload);
+
+        assert!(!redacted.contains("hunter2"));
+        assert!(redacted.contains(REDACTED));
+    }
+
+    #[test]
+    fn test_redact_response() {
+        let body = json!({"resource": {"secret": "fnAAA", "role": "admin"}}).to_string();
+        let redacted: serde_json::Value = serde_json::from_str(&redact(&body)).unwrap();
+
+        assert_eq!(
+            json!({"resource": {"secret": REDACTED, "role": "admin"}}),
+            redacted
+        );
+    }
+
+    #[test]
+    fn test_redact_nothing() {
+        let payload = r#"{"get": "meow"}"#;
+
+        assert!(matches!(redact(payload), Cow::Borrowed(_)));
+        assert_eq!("not json", redact("not json"));
+    }
+}
@@ -1,14 +1,28 @@
 mod index;
+mod key_info;
+mod metrics;
 mod value;
 
 use crate::error::Error;
-use futures::{Future, Poll};
+use futures::{future, Future, Poll};
 
 pub use index::*;
+pub use key_info::*;
+pub use metrics::*;
 pub use value::*;
 
 pub struct FutureResponse<T>(pub Box<dyn Future<Item = T, Error = Error> + Send + 'static>);
 
+impl<T> FutureResponse<T>
+where
+    T: Send + 'static,
+{
+    /// A response failing right away, e.g. when the request can't be built.
+    pub(crate) fn err(error: Error) -> Self {
+        FutureResponse(Box::new(future::err(error)))
+    }
+}
+
 impl<T> Future for FutureResponse<T> {
     type Item = T;
     type Error = Error;
@@ -18,7 +32,111 @@ impl<T> Future for FutureResponse<T> {
     }
 }
 
+/// A successful response from Fauna.
+///
+/// More data from the response headers may be added, so the response can't
+/// be built with a struct literal outside of the crate. Use
+/// [Response::new](#method.new) instead, e.g. for mocking.
 #[derive(Deserialize, Debug, PartialEq)]
+#[non_exhaustive]
 pub struct Response {
     pub resource: Value,
+    #[serde(skip)]
+    pub(crate) schema_version: Option<String>,
+    #[serde(skip)]
+    pub(crate) server_build: Option<String>,
+    #[serde(skip)]
+    pub(crate) metrics: QueryMetrics,
+}
+
+impl Response {
+    /// A response with the `resource` and no header data.
+    pub fn new(resource: Value) -> Self {
+        Self {
+            resource,
+            schema_version: None,
+            server_build: None,
+            metrics: QueryMetrics::default(),
+        }
+    }
+
+    /// A response with the `resource` and the header data of this one.
+    pub(crate) fn with_resource(&self, resource: Value) -> Self {
+        Self {
+            resource,
+            schema_version: self.schema_version.clone(),
+            server_build: self.server_build.clone(),
+            metrics: self.metrics,
+        }
+    }
+
+    /// The value the query returned.
+    pub fn resource(&self) -> &Value {
+        &self.resource
+    }
+
+    /// The user-defined `data` field of the returned value, present e.g. when
+    /// the query returns an instance. `None` if the value has no `data`.
+    pub fn data(&self) -> Option<&Value> {
+        self.resource.get("data")
+    }
+
+    /// The `X-Schema-Version` Fauna reported with the response, if any.
+    pub fn schema_version(&self) -> Option<&str> {
+        self.schema_version.as_deref()
+    }
+
+    /// The `X-FaunaDB-Build` of the server that sent the response, if any.
+    pub fn server_build(&self) -> Option<&str> {
+        self.server_build.as_deref()
+    }
+
+    /// The cost and performance metrics Fauna reported for the query.
+    pub fn metrics(&self) -> &QueryMetrics {
+        &self.metrics
+    }
+
+    /// Consumes the response, returning the value the query returned.
+    pub fn into_resource(self) -> Value {
+        self.resource
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{self, json};
+
+    #[test]
+    fn test_response_data() {
+        let response: Response = serde_json::from_value(json!({
+            "resource": {
+                "ref": {"@ref": {"id": "1", "class": {"@ref": {"id": "classes"}}}},
+                "ts": 1,
+                "data": {"name": "Musti"}
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(Some("Musti"), response.data().unwrap()["name"].as_str());
+        assert_eq!(Some(1), response.resource()["ts"].as_u64());
+    }
+
+    #[test]
+    fn test_response_without_data() {
+        let response: Response = serde_json::from_value(json!({ "resource": 2 })).unwrap();
+
+        assert_eq!(None, response.data());
+        assert_eq!(Value::from(2u64), response.into_resource());
+    }
+
+    #[test]
+    fn test_new_response() {
+        let response = Response::new(Value::from("meow"));
+
+        assert_eq!(Some("meow"), response.resource().as_str());
+        assert_eq!(None, response.schema_version());
+        assert_eq!(None, response.server_build());
+        assert_eq!(&QueryMetrics::default(), response.metrics());
+    }
 }
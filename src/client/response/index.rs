@@ -20,7 +20,7 @@ mod private {
     impl Sealed for usize {}
     impl Sealed for str {}
     impl Sealed for String {}
-    impl<'a, T: ?Sized> Sealed for &'a T where T: Sealed {}
+    impl<T: ?Sized> Sealed for &T where T: Sealed {}
 }
 
 impl ValueIndex for usize {
@@ -65,7 +65,7 @@ impl ValueIndex for String {
     }
 }
 
-impl<'a, T: ?Sized> ValueIndex for &'a T
+impl<T: ?Sized> ValueIndex for &T
 where
     T: ValueIndex,
 {
@@ -0,0 +1,111 @@
+use super::Value;
+use crate::{error::Error, expr::Ref, query::write::Role};
+
+/// The database and role a key gives access to, resolved with
+/// [Client::key_info](../struct.Client.html#method.key_info).
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyInfo {
+    /// The `Ref` of the key instance.
+    pub reference: Ref<'static>,
+    /// The database the key targets. `None` if the key targets the database it
+    /// was created in.
+    pub database: Option<Ref<'static>>,
+    /// The access role of the key.
+    pub role: Role,
+    /// User-defined metadata of the key.
+    pub data: Option<Value>,
+}
+
+impl KeyInfo {
+    pub(crate) fn from_value(value: &Value) -> crate::Result<Self> {
+        let reference = value["ref"]
+            .as_reference()
+            .cloned()
+            .ok_or(Error::ResponseDataFailure("Key instance has no ref".into()))?;
+
+        let database = match value.get("database") {
+            Some(database) => Some(database.as_reference().cloned().ok_or(
+                Error::ResponseDataFailure("Key database is not a ref".into()),
+            )?),
+            None => None,
+        };
+
+        let role: Role = value["role"]
+            .as_str()
+            .and_then(|role| role.parse().ok())
+            .ok_or(Error::ResponseDataFailure("Unknown key role".into()))?;
+
+        let data = value.get("data").cloned();
+
+        Ok(Self {
+            reference,
+            database,
+            role,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, test_utils::*};
+    use serde_json::{self, json};
+
+    #[test]
+    fn test_key_info_from_value() {
+        let value: Value = serde_json::from_value(json!({
+            "ref": {
+                "@ref": {
+                    "id": "1234",
+                    "class": {"@ref": {"id": "keys"}}
+                }
+            },
+            "database": {
+                "@ref": {
+                    "id": "cats",
+                    "class": {"@ref": {"id": "databases"}}
+                }
+            },
+            "role": "server",
+            "priority": 1
+        }))
+        .unwrap();
+
+        let info = KeyInfo::from_value(&value).unwrap();
+
+        assert_eq!("1234", info.reference.id);
+        assert_eq!("databases/cats", info.database.unwrap().path());
+        assert_eq!(Role::Server, info.role);
+        assert_eq!(None, info.data);
+    }
+
+    #[test]
+    fn test_key_info_unknown_role() {
+        let value: Value = serde_json::from_value(json!({
+            "ref": {
+                "@ref": {
+                    "id": "1234",
+                    "class": {"@ref": {"id": "keys"}}
+                }
+            },
+            "role": "superuser"
+        }))
+        .unwrap();
+
+        assert!(KeyInfo::from_value(&value).is_err());
+    }
+
+    #[test]
+    fn test_key_info_eval() {
+        with_database(|db_name| {
+            let params = KeyParams::new(Ref::database(db_name), Role::ServerReadOnly);
+            let response = CLIENT.query(CreateKey::new(params)).unwrap();
+            let secret = response.resource["secret"].as_str().unwrap();
+
+            let info = CLIENT.key_info(secret).unwrap();
+
+            assert_eq!(Ref::database(db_name).path(), info.database.unwrap().path());
+            assert_eq!(Role::ServerReadOnly, info.role);
+        })
+    }
+}
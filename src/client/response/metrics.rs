@@ -0,0 +1,100 @@
+use http::header::HeaderMap;
+use std::time::Duration;
+
+/// The cost and performance metrics Fauna reported in the response headers.
+/// A metric is `None` if its header was missing or not a number.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QueryMetrics {
+    /// `X-Compute-Ops`, the compute operations the query used.
+    pub compute_ops: Option<u64>,
+    /// `X-Query-Time`, the time Fauna spent running the query.
+    pub query_time: Option<Duration>,
+    /// `X-Read-Ops`, the instances read.
+    pub read_ops: Option<u64>,
+    /// `X-Write-Ops`, the instances written.
+    pub write_ops: Option<u64>,
+    /// `X-Byte-Read-Ops`, the billed read operations by the bytes read.
+    pub byte_read_ops: Option<u64>,
+    /// `X-Byte-Write-Ops`, the billed write operations by the bytes written.
+    pub byte_write_ops: Option<u64>,
+    /// `X-Storage-Bytes-Read`, the bytes read from the storage.
+    pub storage_bytes_read: Option<u64>,
+}
+
+impl QueryMetrics {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            compute_ops: header_u64(headers, "x-compute-ops"),
+            query_time: header_u64(headers, "x-query-time").map(Duration::from_millis),
+            read_ops: header_u64(headers, "x-read-ops"),
+            write_ops: header_u64(headers, "x-write-ops"),
+            byte_read_ops: header_u64(headers, "x-byte-read-ops"),
+            byte_write_ops: header_u64(headers, "x-byte-write-ops"),
+            storage_bytes_read: header_u64(headers, "x-storage-bytes-read"),
+        }
+    }
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::HeaderValue;
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-compute-ops", HeaderValue::from_static("3"));
+        headers.insert("x-query-time", HeaderValue::from_static("15"));
+        headers.insert("x-txn-time", HeaderValue::from_static("1565018361852000"));
+
+        let metrics = QueryMetrics::from_headers(&headers);
+
+        assert_eq!(Some(3), metrics.compute_ops);
+        assert_eq!(Some(Duration::from_millis(15)), metrics.query_time);
+    }
+
+    #[test]
+    fn test_from_response_headers() {
+        let response = hyper::Response::builder()
+            .header("X-Read-Ops", "4")
+            .header("X-Write-Ops", "1")
+            .header("X-Byte-Read-Ops", "5")
+            .header("X-Byte-Write-Ops", "2")
+            .header("X-Storage-Bytes-Read", "2048")
+            .header("X-Query-Time", "7")
+            .body(hyper::Body::empty())
+            .unwrap();
+
+        let metrics = QueryMetrics::from_headers(response.headers());
+
+        let expected = QueryMetrics {
+            compute_ops: None,
+            query_time: Some(Duration::from_millis(7)),
+            read_ops: Some(4),
+            write_ops: Some(1),
+            byte_read_ops: Some(5),
+            byte_write_ops: Some(2),
+            storage_bytes_read: Some(2048),
+        };
+
+        assert_eq!(expected, metrics);
+    }
+
+    #[test]
+    fn test_missing_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-compute-ops", HeaderValue::from_static("lots"));
+
+        assert_eq!(
+            QueryMetrics::default(),
+            QueryMetrics::from_headers(&headers)
+        );
+    }
+}
@@ -1,17 +1,22 @@
 use super::ValueIndex;
 use crate::{
-    expr::{Bytes, Number, Ref},
+    expr::{Array, Bytes, Expr, Number, Object, Ref, SimpleExpr},
     serde::base64_bytes,
 };
 use chrono::{DateTime, NaiveDate, Utc};
-use std::collections::BTreeMap;
+use serde::de::DeserializeOwned;
+use std::{borrow::Cow, collections::BTreeMap, convert::TryFrom};
 
 /// Represents any value returned from Fauna.
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/types)
+///
+/// Fauna escapes the objects with keys that look like annotations, such as a
+/// field named `@ref`, as `{"@obj": {...}}`. These are read as plain objects,
+/// with the keys kept as they are.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-#[serde(untagged)]
+#[serde(untagged, from = "TaggedValue")]
 pub enum Value {
     /// A value with an annotation for its type definition.
     Annotated(AnnotatedValue),
@@ -19,6 +24,32 @@ pub enum Value {
     Simple(SimpleValue),
 }
 
+/// The tagged JSON `Value` is read from.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TaggedValue {
+    Escaped(EscapedObject),
+    Annotated(AnnotatedValue),
+    Simple(SimpleValue),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EscapedObject {
+    #[serde(rename = "@obj")]
+    object: BTreeMap<String, Value>,
+}
+
+impl From<TaggedValue> for Value {
+    fn from(tagged: TaggedValue) -> Self {
+        match tagged {
+            TaggedValue::Escaped(escaped) => Value::Simple(SimpleValue::Object(escaped.object)),
+            TaggedValue::Annotated(annotated) => Value::Annotated(annotated),
+            TaggedValue::Simple(simple) => Value::Simple(simple),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum SimpleValue {
@@ -62,11 +93,82 @@ pub enum AnnotatedValue {
     #[serde(rename = "@set")]
     Set(Box<Value>),
     /// Stores an instant in time expressed as a calendar date and time of day
-    /// in UTC.
-    #[serde(rename = "@ts")]
+    /// in UTC. Read from both `@ts` and the FQL v10 `@time` annotations.
+    #[serde(rename = "@ts", alias = "@time")]
     Timestamp(DateTime<Utc>),
 }
 
+/// The definition of a set returned from Fauna as a `@set` value, created with
+/// `Match`.
+///
+/// Holds the index the set was matched from and the terms used, e.g. for
+/// paginating the same set again later.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SetRef {
+    /// The `Ref` of the index.
+    #[serde(rename = "match")]
+    pub matching: Value,
+    /// The search terms, `None` if the index was matched without terms.
+    #[serde(default)]
+    pub terms: Option<Value>,
+}
+
+impl SetRef {
+    /// The `Ref` of the index the set was matched from.
+    pub fn index(&self) -> Option<&Ref<'static>> {
+        self.matching.as_reference()
+    }
+}
+
+/// Converts a value returned from Fauna back into an expression, e.g. for
+/// using a pagination cursor or a returned instance in the next query.
+///
+/// Objects are annotated again, quoted queries stay quoted and a `Set` is
+/// written back as the set function it was returned from.
+impl<'a> From<Value> for Expr<'a> {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Annotated(AnnotatedValue::Ref(r)) => Expr::from(r),
+            Value::Annotated(AnnotatedValue::Query(q)) => raw_expr(*q).into_quoted(),
+            Value::Annotated(AnnotatedValue::Bytes(b)) => Expr::from(b),
+            Value::Annotated(AnnotatedValue::Date(d)) => Expr::from(d),
+            Value::Annotated(AnnotatedValue::Timestamp(ts)) => Expr::from(ts),
+            Value::Annotated(AnnotatedValue::Set(set)) => raw_expr(*set),
+            Value::Simple(SimpleValue::String(s)) => Expr::from(s),
+            Value::Simple(SimpleValue::Number(n)) => Expr::from(n),
+            Value::Simple(SimpleValue::Boolean(b)) => Expr::from(b),
+            Value::Simple(SimpleValue::Array(a)) => Expr::from(Array::from(a)),
+            Value::Simple(SimpleValue::Object(o)) => Expr::from(into_object(o, Expr::from)),
+            Value::Simple(SimpleValue::Null) => Expr::null(),
+        }
+    }
+}
+
+/// An expression with the objects written as function calls, for the contents
+/// of queries and sets.
+fn raw_expr<'a>(value: Value) -> Expr<'a> {
+    match value {
+        Value::Simple(SimpleValue::Object(o)) => {
+            Expr::Simple(SimpleExpr::Object(Box::new(into_object(o, raw_expr))))
+        }
+        Value::Simple(SimpleValue::Array(a)) => {
+            let array: Vec<Expr<'a>> = a.into_iter().map(raw_expr).collect();
+            Expr::from(Array::from(array))
+        }
+        value => Expr::from(value),
+    }
+}
+
+fn into_object<'a, F>(map: BTreeMap<String, Value>, f: F) -> Object<'a>
+where
+    F: Fn(Value) -> Expr<'a>,
+{
+    let map: BTreeMap<Cow<'a, str>, Expr<'a>> =
+        map.into_iter().map(|(k, v)| (Cow::from(k), f(v))).collect();
+
+    Object::from(map)
+}
+
 impl Default for Value {
     fn default() -> Self {
         Value::null()
@@ -126,6 +228,40 @@ where
     }
 }
 
+/// Implements `TryFrom<Value>` with the given accessor, failing with a
+/// `ConversionError` if the value is of another type.
+macro_rules! try_from_value {
+    ($($kind:ty => $accessor:expr, $expected:literal;)*) => (
+        $(
+            impl TryFrom<Value> for $kind {
+                type Error = crate::error::Error;
+
+                fn try_from(value: Value) -> crate::Result<Self> {
+                    $accessor(value).ok_or_else(|| {
+                        crate::error::Error::ConversionError(String::from(concat!(
+                            "Expected ",
+                            $expected
+                        )))
+                    })
+                }
+            }
+        )*
+    );
+}
+
+try_from_value! {
+    String => Value::into_string, "a string";
+    BTreeMap<String, Value> => Value::into_object, "an object";
+    Vec<Value> => Value::into_array, "an array";
+    u64 => |v: Value| v.as_u64(), "an unsigned integer";
+    i64 => |v: Value| v.as_i64(), "an integer";
+    f64 => |v: Value| v.as_f64(), "a float";
+    bool => |v: Value| v.as_bool(), "a boolean";
+    NaiveDate => |v: Value| v.as_date(), "a date";
+    DateTime<Utc> => |v: Value| v.as_timestamp(), "a timestamp";
+    Ref<'static> => |v: Value| v.as_reference().cloned(), "a ref";
+}
+
 impl Value {
     /// A helper to get a `Null` value.
     pub const fn null() -> Value {
@@ -179,12 +315,64 @@ impl Value {
         index.index_into_mut(self)
     }
 
+    /// Index into a Fauna `Array` or `Object` like [get](#method.get), and
+    /// deserialize the value into `T`. The type annotations are dropped before
+    /// deserializing, so e.g. timestamps deserialize from strings.
+    ///
+    /// Returns `None` if the value does not exist or does not deserialize into
+    /// `T`.
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// # use serde_json::json;
+    /// let value: Value = serde_json::from_value(json!({"data": {"lives": 9}})).unwrap();
+    ///
+    /// assert_eq!(Some(9u8), value["data"].get_as("lives"));
+    /// ```
+    pub fn get_as<T, I>(&self, index: I) -> Option<T>
+    where
+        T: DeserializeOwned,
+        I: ValueIndex,
+    {
+        self.get(index)
+            .and_then(|value| crate::client::from_value(value).ok())
+    }
+
+    /// Deserializes the value into `T`, dropping the type annotations like
+    /// [get_as](#method.get_as). The error tells which field failed.
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// # use serde_json::json;
+    /// #[derive(serde_derive::Deserialize)]
+    /// struct Cat {
+    ///     name: String,
+    ///     lives: u8,
+    /// }
+    ///
+    /// let value: Value = serde_json::from_value(json!({"name": "Musti", "lives": 9})).unwrap();
+    /// let cat: Cat = value.to_typed().unwrap();
+    ///
+    /// assert_eq!("Musti", cat.name);
+    /// ```
+    pub fn to_typed<T>(&self) -> crate::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        crate::client::from_value(self)
+    }
+
+    /// Like [to_typed](#method.to_typed), consuming the value.
+    pub fn into_typed<T>(self) -> crate::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.to_typed()
+    }
+
     /// `true` if the `Value` is a `String`.
     pub fn is_string(&self) -> bool {
-        match self {
-            Value::Simple(SimpleValue::String(_)) => true,
-            _ => false,
-        }
+        matches!(self, Value::Simple(SimpleValue::String(_)))
     }
 
     /// Returns a &str if the value is a `String`, otherwise `None`.
@@ -205,10 +393,7 @@ impl Value {
 
     /// `true` if the `Value` is a `Number`.
     pub fn is_number(&self) -> bool {
-        match self {
-            Value::Simple(SimpleValue::Number(_)) => true,
-            _ => false,
-        }
+        matches!(self, Value::Simple(SimpleValue::Number(_)))
     }
 
     /// Returns a `Number` for number values, otherwise `None`.
@@ -267,6 +452,20 @@ impl Value {
         }
     }
 
+    /// Returns a `Decimal` for numbers and for strings holding a decimal
+    /// number, as stored from a `Decimal` expression, otherwise `None`.
+    ///
+    /// Strings are parsed losslessly, doubles are converted from their shortest
+    /// decimal representation.
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Option<rust_decimal::Decimal> {
+        match self {
+            Value::Simple(SimpleValue::Number(num)) => num.as_decimal(),
+            Value::Simple(SimpleValue::String(s)) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
     /// `true` if the `Value` is a `f32`.
     pub fn is_f32(&self) -> bool {
         match self {
@@ -285,10 +484,7 @@ impl Value {
 
     /// `true` if the `Value` is a `bool`.
     pub fn is_bool(&self) -> bool {
-        match self {
-            Value::Simple(SimpleValue::Boolean(_)) => true,
-            _ => false,
-        }
+        matches!(self, Value::Simple(SimpleValue::Boolean(_)))
     }
 
     /// Returns a `bool` for `bool` values, otherwise `None`.
@@ -301,10 +497,7 @@ impl Value {
 
     /// `true` if the `Value` is an `Array`.
     pub fn is_array(&self) -> bool {
-        match self {
-            Value::Simple(SimpleValue::Array(_)) => true,
-            _ => false,
-        }
+        matches!(self, Value::Simple(SimpleValue::Array(_)))
     }
 
     /// Returns an `Array` for `Array` values, otherwise `None`.
@@ -324,8 +517,8 @@ impl Value {
     }
 
     /// Returns a mutable `Array` for `Array` values, otherwise `None`.
-    pub fn as_array_mut(&mut self) -> Option<&Vec<Value>> {
-        match self {
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Value>> {
+        match *self {
             Value::Simple(SimpleValue::Array(ref mut v)) => Some(v),
             _ => None,
         }
@@ -333,10 +526,7 @@ impl Value {
 
     /// `true` if the `Value` is an `Object`.
     pub fn is_object(&self) -> bool {
-        match self {
-            Value::Simple(SimpleValue::Object(_)) => true,
-            _ => false,
-        }
+        matches!(self, Value::Simple(SimpleValue::Object(_)))
     }
 
     /// Returns an `Object` for `Object` values, otherwise `None`.
@@ -365,24 +555,18 @@ impl Value {
 
     /// `true` if the `Value` is `Null`.
     pub fn is_null(&self) -> bool {
-        match self {
-            Value::Simple(SimpleValue::Null) => true,
-            _ => false,
-        }
+        matches!(self, Value::Simple(SimpleValue::Null))
     }
 
     /// `true` if the `Value` is a `Ref`.
     pub fn is_reference(&self) -> bool {
-        match self {
-            Value::Annotated(AnnotatedValue::Ref(_)) => true,
-            _ => false,
-        }
+        matches!(self, Value::Annotated(AnnotatedValue::Ref(_)))
     }
 
     /// Returns a `Ref` for `Ref` values, otherwise `None`.
     pub fn as_reference(&self) -> Option<&Ref<'static>> {
         match self {
-            Value::Annotated(AnnotatedValue::Ref(reference)) => Some(&*reference),
+            Value::Annotated(AnnotatedValue::Ref(reference)) => Some(reference),
             _ => None,
         }
     }
@@ -395,26 +579,20 @@ impl Value {
 
     /// `true` if the `Value` is a `Query`.
     pub fn is_query(&self) -> bool {
-        match self {
-            Value::Annotated(AnnotatedValue::Query(_)) => true,
-            _ => false,
-        }
+        matches!(self, Value::Annotated(AnnotatedValue::Query(_)))
     }
 
     /// Returns a `Query` for `Query` values, otherwise `None`.
     pub fn as_query(&self) -> Option<&Value> {
         match self {
-            Value::Annotated(AnnotatedValue::Query(q)) => Some(&*q),
+            Value::Annotated(AnnotatedValue::Query(q)) => Some(q),
             _ => None,
         }
     }
 
     /// `true` if the `Value` is a set of `Bytes`.
     pub fn is_bytes(&self) -> bool {
-        match self {
-            Value::Annotated(AnnotatedValue::Bytes(_)) => true,
-            _ => false,
-        }
+        matches!(self, Value::Annotated(AnnotatedValue::Bytes(_)))
     }
 
     /// Returns `Bytes` for sets of `Bytes`, otherwise `None`.
@@ -425,12 +603,14 @@ impl Value {
         }
     }
 
+    /// Returns the `Bytes` as a lower case hex string, otherwise `None`.
+    pub fn as_hex(&self) -> Option<String> {
+        self.as_bytes().map(Bytes::to_hex)
+    }
+
     /// `true` if the `Value` is a `Date`.
     pub fn is_date(&self) -> bool {
-        match self {
-            Value::Annotated(AnnotatedValue::Date(_)) => true,
-            _ => false,
-        }
+        matches!(self, Value::Annotated(AnnotatedValue::Date(_)))
     }
 
     /// Returns a `NaiveDate` for `Date` values, otherwise `None`.
@@ -443,26 +623,31 @@ impl Value {
 
     /// `true` if the `Value` is a `Set`.
     pub fn is_set(&self) -> bool {
-        match self {
-            Value::Annotated(AnnotatedValue::Set(_)) => true,
-            _ => false,
-        }
+        matches!(self, Value::Annotated(AnnotatedValue::Set(_)))
     }
 
     /// Returns a `Set` for `Set` values, otherwise `None`.
     pub fn as_set(&self) -> Option<&Value> {
         match self {
-            Value::Annotated(AnnotatedValue::Set(set)) => Some(&*set),
+            Value::Annotated(AnnotatedValue::Set(set)) => Some(set),
             _ => None,
         }
     }
 
+    /// Returns a [SetRef](struct.SetRef.html) for `Set` values created with
+    /// `Match`, otherwise `None`.
+    pub fn as_set_ref(&self) -> Option<SetRef> {
+        let set = self.as_set()?.as_object()?;
+
+        Some(SetRef {
+            matching: set.get("match")?.clone(),
+            terms: set.get("terms").filter(|t| !t.is_null()).cloned(),
+        })
+    }
+
     /// `true` if the `Value` is a `Timestamp`.
     pub fn is_timestamp(&self) -> bool {
-        match self {
-            Value::Annotated(AnnotatedValue::Timestamp(_)) => true,
-            _ => false,
-        }
+        matches!(self, Value::Annotated(AnnotatedValue::Timestamp(_)))
     }
 
     /// Returns a `DateTime` for `Timestamp` values, otherwise `None`.
@@ -473,3 +658,342 @@ impl Value {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, prelude::*};
+    use chrono::{DateTime, NaiveDate, Timelike, Utc};
+    use serde_json::{self, json};
+    use std::{collections::BTreeMap, convert::TryFrom};
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Cat {
+        name: String,
+        age: u64,
+        born: NaiveDate,
+        owner: Owner,
+    }
+
+    #[test]
+    fn test_try_from_i64() {
+        let value: Value = serde_json::from_value(json!(-3)).unwrap();
+        assert_eq!(-3, i64::try_from(value).unwrap());
+
+        match i64::try_from(Value::from("meow")) {
+            Err(Error::ConversionError(message)) => assert_eq!("Expected an integer", message),
+            result => panic!("Expected a conversion error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_try_from_timestamp() {
+        let value: Value = serde_json::from_value(json!({"@ts": "2019-05-26T16:20:00Z"})).unwrap();
+        let expected: DateTime<Utc> = "2019-05-26T16:20:00Z".parse().unwrap();
+
+        assert_eq!(expected, DateTime::<Utc>::try_from(value).unwrap());
+        assert!(DateTime::<Utc>::try_from(Value::from(1u64)).is_err());
+    }
+
+    #[test]
+    fn test_try_from_other_scalars() {
+        let value: Value = serde_json::from_value(json!({
+            "ref": {"@ref": {"id": "musti"}},
+            "born": {"@date": "2011-07-07"},
+            "chonky": true,
+            "weight": 4.5,
+            "lives": 9
+        }))
+        .unwrap();
+
+        let mut object = BTreeMap::try_from(value).unwrap();
+
+        assert_eq!(
+            Ref::instance("musti"),
+            Ref::try_from(object.remove("ref").unwrap()).unwrap()
+        );
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2011, 7, 7).unwrap(),
+            NaiveDate::try_from(object.remove("born").unwrap()).unwrap()
+        );
+        assert!(bool::try_from(object.remove("chonky").unwrap()).unwrap());
+        assert_eq!(
+            4.5,
+            f64::try_from(object.remove("weight").unwrap()).unwrap()
+        );
+        assert_eq!(9, u64::try_from(object.remove("lives").unwrap()).unwrap());
+        assert!(String::try_from(Value::from(9u64)).is_err());
+    }
+
+    #[test]
+    fn test_into_typed() {
+        let value: Value = serde_json::from_value(json!({
+            "name": "Musti",
+            "age": 7,
+            "born": {"@date": "2011-07-07"},
+            "owner": {"name": "Naukio", "since": {"@ts": "2019-05-26T16:20:00Z"}}
+        }))
+        .unwrap();
+
+        let expected = Cat {
+            name: String::from("Musti"),
+            age: 7,
+            born: NaiveDate::from_ymd_opt(2011, 7, 7).unwrap(),
+            owner: Owner {
+                name: String::from("Naukio"),
+                since: "2019-05-26T16:20:00Z".parse().unwrap(),
+            },
+        };
+
+        assert_eq!(expected, value.to_typed().unwrap());
+        assert_eq!(expected, value.into_typed().unwrap());
+    }
+
+    #[test]
+    fn test_into_typed_error() {
+        let value: Value = serde_json::from_value(json!({"name": "Musti"})).unwrap();
+
+        match value.into_typed::<Cat>() {
+            Err(Error::ConversionError(message)) => assert!(message.contains("age")),
+            result => panic!("Expected a conversion error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn test_decimal_round_trip() {
+        for s in &[
+            "0.1",
+            "1234567890123456.78",
+            "7922816251426433759354395033.5",
+        ] {
+            let decimal: rust_decimal::Decimal = s.parse().unwrap();
+
+            let json = serde_json::to_value(Expr::from(decimal)).unwrap();
+            let value: Value = serde_json::from_value(json).unwrap();
+
+            assert_eq!(Some(decimal), value.as_decimal());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn test_decimal_from_numbers() {
+        let value: Value = serde_json::from_value(json!(0.1)).unwrap();
+        assert_eq!(Some("0.1".parse().unwrap()), value.as_decimal());
+
+        let value: Value = serde_json::from_value(json!(-42)).unwrap();
+        assert_eq!(Some(rust_decimal::Decimal::from(-42)), value.as_decimal());
+
+        let value: Value = serde_json::from_value(json!("cat")).unwrap();
+        assert_eq!(None, value.as_decimal());
+    }
+
+    #[test]
+    fn test_value_into_expr() {
+        let returned = json!({
+            "ref": {
+                "@ref": {
+                    "id": "1",
+                    "class": {"@ref": {"id": "cats", "class": {"@ref": {"id": "classes"}}}}
+                }
+            },
+            "data": {
+                "name": "Musti",
+                "born": {"@date": "2011-07-07"},
+                "friends": [1, {"@ts": "2015-02-20T06:30:00Z"}, null],
+                "spells": {"@set": {"match": {"@ref": {"id": "spells", "class": {"@ref": {"id": "indexes"}}}}, "terms": "fire"}},
+                "fun": {"@query": {"lambda": "x", "expr": {"var": "x"}}}
+            }
+        });
+
+        let value: Value = serde_json::from_value(returned).unwrap();
+        let serialized = serde_json::to_value(Expr::from(value)).unwrap();
+
+        let expected = json!({
+            "object": {
+                "ref": {
+                    "@ref": {
+                        "id": "1",
+                        "class": {"@ref": {"id": "cats", "class": {"@ref": {"id": "classes"}}}}
+                    }
+                },
+                "data": {
+                    "object": {
+                        "name": "Musti",
+                        "born": {"@date": "2011-07-07"},
+                        "friends": [1, {"@ts": "2015-02-20T06:30:00Z"}, null],
+                        "spells": {"match": {"@ref": {"id": "spells", "class": {"@ref": {"id": "indexes"}}}}, "terms": "fire"},
+                        "fun": {"@query": {"lambda": "x", "expr": {"var": "x"}}}
+                    }
+                }
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_timestamp_round_trip_keeps_nanoseconds() {
+        let json = json!({ "@ts": "2019-05-26T16:20:00.123456789Z" });
+        let value: Value = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(123_456_789, value.as_timestamp().unwrap().nanosecond());
+
+        let serialized = serde_json::to_value(Expr::from(value)).unwrap();
+
+        assert_eq!(json, serialized);
+    }
+
+    #[test]
+    fn test_escaped_object_with_annotation_keys() {
+        let json = json!({
+            "@obj": {
+                "@ref": {"id": "1"},
+                "@ts": "2019-05-26T16:20:00Z",
+                "name": "Musti"
+            }
+        });
+
+        let value: Value = serde_json::from_value(json).unwrap();
+
+        assert!(value.is_object());
+        assert!(!value["@ref"].is_reference());
+        assert_eq!(Some("1"), value["@ref"]["id"].as_str());
+        assert_eq!(Some("2019-05-26T16:20:00Z"), value["@ts"].as_str());
+        assert_eq!(Some("Musti"), value["name"].as_str());
+    }
+
+    #[test]
+    fn test_object_with_annotation_key_as_data() {
+        let json = json!({"@ref": "not a ref", "name": "Musti"});
+        let value: Value = serde_json::from_value(json).unwrap();
+
+        assert!(value.is_object());
+        assert_eq!(Some("not a ref"), value["@ref"].as_str());
+
+        let nested: Value = serde_json::from_value(json!({
+            "data": {"@obj": {"@ref": "not a ref"}}
+        }))
+        .unwrap();
+
+        assert_eq!(Some("not a ref"), nested["data"]["@ref"].as_str());
+    }
+
+    #[test]
+    fn test_as_array_mut() {
+        let mut value = Value::from(vec![1, 2]);
+
+        value.as_array_mut().unwrap().push(Value::from(3));
+
+        assert_eq!(Value::from(vec![1, 2, 3]), value);
+        assert!(Value::from("meow").as_array_mut().is_none());
+    }
+
+    #[test]
+    fn test_as_hex() {
+        let value: Value = serde_json::from_value(json!({ "@bytes": "yv4=" })).unwrap();
+
+        assert_eq!(Some(String::from("cafe")), value.as_hex());
+        assert_eq!(None, Value::from("cafe").as_hex());
+    }
+
+    #[test]
+    fn test_timestamp_annotations() {
+        let ts: Value = serde_json::from_value(json!({ "@ts": "2019-05-26T16:20:00Z" })).unwrap();
+        let time: Value =
+            serde_json::from_value(json!({ "@time": "2019-05-26T16:20:00Z" })).unwrap();
+
+        assert!(ts.as_timestamp().is_some());
+        assert_eq!(ts, time);
+
+        assert_eq!(
+            json!({ "@ts": "2019-05-26T16:20:00Z" }),
+            serde_json::to_value(Expr::from(time)).unwrap()
+        );
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Owner {
+        name: String,
+        since: chrono::DateTime<chrono::Utc>,
+    }
+
+    #[test]
+    fn test_get_as() {
+        let json = json!({
+            "data": {
+                "name": "Musti",
+                "owner": {"name": "Naukio", "since": {"@ts": "2019-05-26T16:20:00Z"}},
+                "lives": [9, 8],
+            }
+        });
+
+        let value: Value = serde_json::from_value(json).unwrap();
+        let data = &value["data"];
+
+        let owner = Owner {
+            name: String::from("Naukio"),
+            since: "2019-05-26T16:20:00Z".parse().unwrap(),
+        };
+
+        assert_eq!(Some(owner), data.get_as("owner"));
+        assert_eq!(Some(String::from("Musti")), data.get_as("name"));
+        assert_eq!(Some(vec![9u8, 8]), data.get_as("lives"));
+        assert_eq!(Some(8u8), data["lives"].get_as(1));
+
+        assert_eq!(None, data.get_as::<Owner, _>("name"));
+        assert_eq!(None, data.get_as::<String, _>("age"));
+    }
+
+    #[test]
+    fn test_set_ref() {
+        let value: Value = serde_json::from_value(json!({
+            "@set": {
+                "match": {
+                    "@ref": {
+                        "id": "spells_by_element",
+                        "index": {"@ref": {"id": "indexes"}}
+                    }
+                },
+                "terms": "fire"
+            }
+        }))
+        .unwrap();
+
+        let set = value.as_set_ref().unwrap();
+
+        assert_eq!(Some(&Ref::index("spells_by_element")), set.index());
+        assert_eq!(Some(Value::from("fire")), set.terms);
+    }
+
+    #[test]
+    fn test_set_ref_without_terms() {
+        let value: Value = serde_json::from_value(json!({
+            "@set": {
+                "match": {
+                    "@ref": {
+                        "id": "all_spells",
+                        "index": {"@ref": {"id": "indexes"}}
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let set = value.as_set_ref().unwrap();
+
+        assert_eq!(Some(&Ref::index("all_spells")), set.index());
+        assert_eq!(None, set.terms);
+    }
+
+    #[test]
+    fn test_union_set_is_not_a_set_ref() {
+        let value: Value = serde_json::from_value(json!({
+            "@set": {"union": []}
+        }))
+        .unwrap();
+
+        assert!(value.is_set());
+        assert_eq!(None, value.as_set_ref());
+    }
+}
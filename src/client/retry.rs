@@ -0,0 +1,152 @@
+use super::transport::{Transport, TransportFuture};
+use crate::error::Error;
+use futures::{
+    future::{self, Loop},
+    stream::Stream,
+    Future,
+};
+use hyper::{Body, Request, StatusCode};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio_timer::Delay;
+
+/// How many times and how soon a request is sent again after Fauna responds
+/// with `429 Too Many Requests` or `503 Service Unavailable`. Set with
+/// [ClientBuilder::retry_policy](struct.ClientBuilder.html#method.retry_policy).
+///
+/// The wait before a retry doubles from the `backoff` on every attempt. The
+/// retries count to the timeout of the request.
+///
+/// A `502 Bad Gateway` or a `504 Gateway Timeout` doesn't tell if the query
+/// was run or not, so they are retried only for the queries sent with
+/// [Client::query_idempotent](struct.Client.html#method.query_idempotent).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    backoff: Duration,
+}
+
+/// Marks a request safe to send again after a gateway error, set in the
+/// extensions of the request.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Idempotent;
+
+impl Default for RetryPolicy {
+    /// No retries.
+    fn default() -> Self {
+        Self::new(0, Duration::from_millis(100))
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            backoff,
+        }
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// The wait before the given retry, starting from zero.
+    fn delay(&self, retry: u32) -> Duration {
+        self.backoff * 2u32.saturating_pow(retry.min(16))
+    }
+
+    /// Sends the request through the transport, retrying by the policy.
+    pub(crate) fn send(
+        &self,
+        transport: Arc<dyn Transport>,
+        request: Request<Body>,
+    ) -> TransportFuture {
+        if self.max_retries == 0 {
+            return transport.call(request);
+        }
+
+        let policy = *self;
+        let idempotent = request.extensions().get::<Idempotent>().is_some();
+        let (parts, body) = request.into_parts();
+
+        let sending = body
+            .concat2()
+            .map_err(|e| Error::ConnectionError(e.into()))
+            .and_then(move |payload| {
+                let payload = payload.into_bytes();
+
+                future::loop_fn(0, move |retry| {
+                    let mut request = Request::new(Body::from(payload.clone()));
+
+                    *request.method_mut() = parts.method.clone();
+                    *request.uri_mut() = parts.uri.clone();
+                    *request.version_mut() = parts.version;
+                    *request.headers_mut() = parts.headers.clone();
+
+                    transport.call(request).and_then(move |response| {
+                        if retry < policy.max_retries && retriable(response.status(), idempotent) {
+                            trace!(
+                                "Got status {}, retrying in {:?}",
+                                response.status(),
+                                policy.delay(retry)
+                            );
+
+                            let waiting = Delay::new(Instant::now() + policy.delay(retry))
+                                .map_err(|e| Error::ConnectionError(e.into()))
+                                .map(move |_| Loop::Continue(retry + 1));
+
+                            future::Either::A(waiting)
+                        } else {
+                            future::Either::B(future::ok(Loop::Break(response)))
+                        }
+                    })
+                })
+            });
+
+        Box::new(sending)
+    }
+}
+
+fn retriable(status: StatusCode, idempotent: bool) -> bool {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => true,
+        StatusCode::BAD_GATEWAY | StatusCode::GATEWAY_TIMEOUT => idempotent,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(10));
+
+        assert_eq!(Duration::from_millis(10), policy.delay(0));
+        assert_eq!(Duration::from_millis(20), policy.delay(1));
+        assert_eq!(Duration::from_millis(40), policy.delay(2));
+    }
+
+    #[test]
+    fn test_retriable_statuses() {
+        assert!(retriable(StatusCode::SERVICE_UNAVAILABLE, false));
+        assert!(retriable(StatusCode::TOO_MANY_REQUESTS, false));
+        assert!(!retriable(StatusCode::BAD_REQUEST, true));
+        assert!(!retriable(StatusCode::INTERNAL_SERVER_ERROR, true));
+    }
+
+    #[test]
+    fn test_gateway_errors_need_idempotent() {
+        assert!(!retriable(StatusCode::BAD_GATEWAY, false));
+        assert!(!retriable(StatusCode::GATEWAY_TIMEOUT, false));
+        assert!(retriable(StatusCode::BAD_GATEWAY, true));
+        assert!(retriable(StatusCode::GATEWAY_TIMEOUT, true));
+    }
+}
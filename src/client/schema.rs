@@ -0,0 +1,84 @@
+use http::header::HeaderMap;
+use std::sync::Mutex;
+
+/// The header Fauna reports the schema version in.
+pub(crate) const SCHEMA_VERSION_HEADER: &str = "x-schema-version";
+
+pub(crate) type SchemaCallback = Box<dyn Fn(Option<&str>, &str) + Send + Sync>;
+
+/// Tracks the schema version reported by Fauna, calling the callback when it
+/// changes.
+pub(crate) struct SchemaWatch {
+    last_version: Mutex<Option<String>>,
+    on_change: Option<SchemaCallback>,
+}
+
+impl SchemaWatch {
+    pub(crate) fn new(on_change: Option<SchemaCallback>) -> Self {
+        Self {
+            last_version: Mutex::new(None),
+            on_change,
+        }
+    }
+
+    /// Reads the schema version from the headers, returning it if present.
+    pub(crate) fn observe(&self, headers: &HeaderMap) -> Option<String> {
+        let version = schema_version(headers)?;
+        let mut last_version = self.last_version.lock().unwrap();
+
+        if last_version.as_ref() != Some(&version) {
+            if let Some(ref on_change) = self.on_change {
+                on_change(last_version.as_deref(), &version);
+            }
+
+            *last_version = Some(version.clone());
+        }
+
+        Some(version)
+    }
+}
+
+pub(crate) fn schema_version(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(SCHEMA_VERSION_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::HeaderValue;
+    use std::sync::{Arc, Mutex};
+
+    fn headers(version: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(SCHEMA_VERSION_HEADER, HeaderValue::from_static(version));
+        headers
+    }
+
+    #[test]
+    fn test_schema_changes() {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let recorded = changes.clone();
+
+        let watch = SchemaWatch::new(Some(Box::new(move |old, new| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((old.map(String::from), String::from(new)));
+        })));
+
+        assert_eq!(Some(String::from("1")), watch.observe(&headers("1")));
+        watch.observe(&headers("1"));
+        watch.observe(&HeaderMap::new());
+        watch.observe(&headers("2"));
+
+        let expected = vec![
+            (None, String::from("1")),
+            (Some(String::from("1")), String::from("2")),
+        ];
+
+        assert_eq!(expected, *changes.lock().unwrap());
+    }
+}
@@ -0,0 +1,52 @@
+use http::header::HeaderMap;
+use std::sync::Mutex;
+
+/// The header Fauna reports the build of the server in.
+pub(crate) const SERVER_BUILD_HEADER: &str = "x-faunadb-build";
+
+/// Remembers the last server build reported by Fauna.
+#[derive(Default)]
+pub(crate) struct ServerBuild {
+    last_build: Mutex<Option<String>>,
+}
+
+impl ServerBuild {
+    /// Reads the build from the headers, keeping the last one seen if not
+    /// present.
+    pub(crate) fn observe(&self, headers: &HeaderMap) {
+        if let Some(build) = server_build(headers) {
+            *self.last_build.lock().unwrap() = Some(build);
+        }
+    }
+
+    pub(crate) fn last(&self) -> Option<String> {
+        self.last_build.lock().unwrap().clone()
+    }
+}
+
+pub(crate) fn server_build(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(SERVER_BUILD_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::HeaderValue;
+
+    #[test]
+    fn test_keeps_last_build() {
+        let build = ServerBuild::default();
+        assert_eq!(None, build.last());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(SERVER_BUILD_HEADER, HeaderValue::from_static("2.7.0-abc"));
+
+        build.observe(&headers);
+        build.observe(&HeaderMap::new());
+
+        assert_eq!(Some(String::from("2.7.0-abc")), build.last());
+    }
+}
@@ -0,0 +1,157 @@
+use super::{Client, FutureResponse, Response};
+use crate::{expr::Expr, query::basic::At};
+use chrono::{DateTime, Utc};
+
+#[cfg(feature = "sync_client")]
+use super::SyncClient;
+
+/// A handle running every query at the same point in time, wrapping them in
+/// [At](../query/basic/struct.At.html). Created with
+/// [Client::snapshot](struct.Client.html#method.snapshot).
+///
+/// Only reads are allowed in the past, so writes through the snapshot fail.
+#[derive(Clone)]
+pub struct Snapshot<'c> {
+    client: &'c Client,
+    timestamp: DateTime<Utc>,
+}
+
+impl<'c> Snapshot<'c> {
+    pub(crate) fn new(client: &'c Client, timestamp: DateTime<Utc>) -> Self {
+        Self { client, timestamp }
+    }
+
+    /// The point in time of the reads.
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// Send a query, reading the data as it was at the snapshot time.
+    pub fn query<'a, Q>(&self, query: Q) -> FutureResponse<Response>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        self.client.query(At::new(self.timestamp, query))
+    }
+}
+
+/// A synchronous [Snapshot](struct.Snapshot.html), created with
+/// [SyncClient::snapshot](struct.SyncClient.html#method.snapshot).
+#[cfg(feature = "sync_client")]
+pub struct SyncSnapshot<'c> {
+    client: &'c SyncClient,
+    timestamp: DateTime<Utc>,
+}
+
+#[cfg(feature = "sync_client")]
+impl<'c> SyncSnapshot<'c> {
+    pub(crate) fn new(client: &'c SyncClient, timestamp: DateTime<Utc>) -> Self {
+        Self { client, timestamp }
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    pub fn query<'a, Q>(&self, query: Q) -> crate::Result<Response>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        self.client.query(At::new(self.timestamp, query))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, test_utils::*};
+    use chrono::{offset::TimeZone, Utc};
+    use serde_json::{self, json};
+
+    #[test]
+    fn test_snapshot_reads() {
+        let transport =
+            MockTransport::new(200, r#"{"resource": "Musti"}"#).then(r#"{"resource": "Naukio"}"#);
+
+        let requests = transport.requests();
+        let client = mock_client(transport, |_| ());
+
+        let timestamp = Utc.timestamp_opt(60, 0).unwrap();
+        let snapshot = client.snapshot(timestamp);
+
+        assert_eq!(timestamp, snapshot.timestamp());
+
+        let mut musti = Ref::instance("1");
+        musti.set_class("cats");
+
+        let mut naukio = Ref::instance("2");
+        naukio.set_class("cats");
+
+        let first = snapshot
+            .query(Select::new(
+                Array::from(vec!["data", "name"]),
+                Get::instance(musti),
+            ))
+            .unwrap();
+
+        let second = snapshot
+            .query(Select::new(
+                Array::from(vec!["data", "name"]),
+                Get::instance(naukio),
+            ))
+            .unwrap();
+
+        assert_eq!(Some("Musti"), first.resource.as_str());
+        assert_eq!(Some("Naukio"), second.resource.as_str());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(2, requests.len());
+
+        for request in requests.iter() {
+            let payload: serde_json::Value = serde_json::from_str(request.body()).unwrap();
+
+            assert_eq!(json!({"@ts": "1970-01-01T00:01:00Z"}), payload["at"]);
+            assert!(payload["expr"]["select"].is_array());
+        }
+    }
+
+    #[test]
+    fn test_snapshot_reads_eval() {
+        with_class(|client, class_name| {
+            let mut data = Object::default();
+            data.insert("name", "Musti");
+
+            let response = client
+                .query(Create::new(Ref::class(class_name), data))
+                .unwrap();
+
+            let reference = response.resource["ref"].as_reference().unwrap().clone();
+            let created_at = response.resource["ts"].as_u64().unwrap();
+            let timestamp = Utc.timestamp_nanos(created_at as i64 * 1000);
+
+            let mut data = Object::default();
+            data.insert("name", "Naukio");
+
+            client
+                .compare_and_update(reference.clone(), created_at, data)
+                .unwrap();
+
+            let snapshot = client.snapshot(timestamp);
+            let name = snapshot
+                .query(Select::new(
+                    Array::from(vec!["data", "name"]),
+                    Get::instance(reference.clone()),
+                ))
+                .unwrap();
+
+            let ts = snapshot
+                .query(Select::new(
+                    Array::from(vec!["ts"]),
+                    Get::instance(reference),
+                ))
+                .unwrap();
+
+            assert_eq!(Some("Musti"), name.resource.as_str());
+            assert_eq!(Some(created_at), ts.resource.as_u64());
+        })
+    }
+}
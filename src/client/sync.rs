@@ -1,5 +1,9 @@
-use super::{Client, Response};
-use crate::expr::Expr;
+use super::{Client, KeyInfo, OnOverflow, OutputFormat, Response, SyncSnapshot};
+use crate::{
+    expr::{Expr, Ref},
+    query::{read::Projection, write::Role},
+};
+use chrono::{DateTime, Utc};
 use std::sync::Mutex;
 use tokio::runtime::Runtime;
 
@@ -26,4 +30,315 @@ impl SyncClient {
             .unwrap()
             .block_on(self.inner.query(query))
     }
+
+    pub fn query_with_bindings<'a, Q, B, V, E>(
+        &self,
+        template: Q,
+        bindings: B,
+    ) -> crate::Result<Response>
+    where
+        Q: Into<Expr<'a>>,
+        B: IntoIterator<Item = (V, E)>,
+        V: Into<std::borrow::Cow<'a, str>>,
+        E: Into<Expr<'a>>,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.query_with_bindings(template, bindings))
+    }
+
+    pub fn query_with_timeout<'a, Q>(
+        &self,
+        query: Q,
+        timeout: std::time::Duration,
+    ) -> crate::Result<Response>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.query_with_timeout(query, timeout))
+    }
+
+    pub fn query_idempotent<'a, Q>(&self, query: Q) -> crate::Result<Response>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.query_idempotent(query))
+    }
+
+    pub fn query_with_format<'a, Q>(
+        &self,
+        query: Q,
+        format: OutputFormat,
+    ) -> crate::Result<Response>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.query_with_format(query, format))
+    }
+
+    pub fn query_timed<'a, Q>(&self, query: Q) -> crate::Result<(Response, std::time::Duration)>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.query_timed(query))
+    }
+
+    pub fn query_detailed<'a, Q>(
+        &self,
+        query: Q,
+    ) -> crate::Result<(Response, http::StatusCode, http::HeaderMap)>
+    where
+        Q: Into<Expr<'a>>,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.query_detailed(query))
+    }
+
+    pub fn snapshot(&self, timestamp: DateTime<Utc>) -> SyncSnapshot<'_> {
+        SyncSnapshot::new(self, timestamp)
+    }
+
+    pub fn server_version(&self) -> Option<String> {
+        self.inner.server_version()
+    }
+
+    /// Waits for the requests in flight to finish and drops the client.
+    pub fn shutdown(self) -> crate::Result<()> {
+        let mut runtime = self.runtime.into_inner().unwrap();
+        runtime.block_on(self.inner.shutdown())
+    }
+
+    pub fn warm_up(&self) -> crate::Result<()> {
+        self.runtime.lock().unwrap().block_on(self.inner.warm_up())
+    }
+
+    pub fn with_secret(&self, secret: &str) -> crate::Result<SyncClient> {
+        SyncClient::new(self.inner.with_secret(secret))
+    }
+
+    pub fn bootstrap(&self, name: &str) -> crate::Result<SyncClient> {
+        let client = self
+            .runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.bootstrap(name))?;
+
+        SyncClient::new(client)
+    }
+
+    pub fn change_password(&self, reference: Ref<'_>, password: &str) -> crate::Result<Response> {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.change_password(reference, password))
+    }
+
+    pub fn change_password_and_login(
+        &self,
+        reference: Ref<'_>,
+        password: &str,
+    ) -> crate::Result<String> {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.change_password_and_login(reference, password))
+    }
+
+    pub fn key_info(&self, secret: &str) -> crate::Result<KeyInfo> {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.key_info(secret))
+    }
+
+    pub fn query_all<'a, I, Q>(&self, queries: I) -> crate::Result<Vec<Response>>
+    where
+        I: IntoIterator<Item = Q>,
+        Q: Into<Expr<'a>>,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.query_all(queries))
+    }
+
+    pub fn query_batch_as<'a, T, Q>(&self, queries: Vec<Q>) -> crate::Result<Vec<crate::Result<T>>>
+    where
+        T: serde::de::DeserializeOwned + Send + Sync + 'static,
+        Q: Into<Expr<'a>>,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.query_batch_as(queries))
+    }
+
+    pub fn create_database_with_key(
+        &self,
+        name: &str,
+        role: Role,
+    ) -> crate::Result<(Ref<'static>, String)> {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.create_database_with_key(name, role))
+    }
+
+    pub fn scan_range<'a, T>(
+        &self,
+        index: impl Into<Expr<'a>>,
+        from: impl Into<Expr<'a>>,
+        to: impl Into<Expr<'a>>,
+        lambda: impl Into<Expr<'a>>,
+    ) -> crate::Result<Vec<T>>
+    where
+        T: serde::de::DeserializeOwned + Send + Sync + 'static,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.scan_range(index, from, to, lambda))
+    }
+
+    pub fn upsert<'a>(
+        &self,
+        unique_index: impl Into<Expr<'a>>,
+        terms: impl Into<Expr<'a>>,
+        class_ref: impl Into<Expr<'a>>,
+        data: impl Into<Expr<'a>>,
+    ) -> crate::Result<Response> {
+        self.runtime.lock().unwrap().block_on(self.inner.upsert(
+            unique_index,
+            terms,
+            class_ref,
+            data,
+        ))
+    }
+
+    pub fn create_returning_ref<'a>(
+        &self,
+        class_ref: impl Into<Expr<'a>>,
+        data: impl Into<Expr<'a>>,
+    ) -> crate::Result<Ref<'static>> {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.create_returning_ref(class_ref, data))
+    }
+
+    pub fn compare_and_update<'a>(
+        &self,
+        reference: impl Into<Expr<'a>>,
+        expected_ts: u64,
+        data: impl Into<Expr<'a>>,
+    ) -> crate::Result<Response> {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.compare_and_update(reference, expected_ts, data))
+    }
+
+    pub fn project_as<T>(&self, projection: Projection) -> crate::Result<T>
+    where
+        T: serde::de::DeserializeOwned + Send + Sync + 'static,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.project_as(projection))
+    }
+
+    pub fn count_set<'a>(&self, set: impl Into<Expr<'a>>) -> crate::Result<u64> {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.count_set(set))
+    }
+
+    pub fn collect_set_as<'a, T>(
+        &self,
+        set: impl Into<Expr<'a>>,
+        max_items: usize,
+        on_overflow: OnOverflow,
+    ) -> crate::Result<Vec<T>>
+    where
+        T: serde::de::DeserializeOwned + Send + Sync + 'static,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.collect_set_as(set, max_items, on_overflow))
+    }
+
+    pub fn read_window<'a>(
+        &self,
+        set: impl Into<Expr<'a>>,
+        offset: u32,
+        limit: u32,
+    ) -> crate::Result<Response> {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.read_window(set, offset, limit))
+    }
+
+    #[cfg(feature = "v10")]
+    pub fn query_fql<'a>(
+        &self,
+        fql: &str,
+        args: crate::expr::Object<'a>,
+    ) -> crate::Result<super::FqlResponse> {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.query_fql(fql, args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, test_utils::*};
+    use tokio::runtime::Runtime;
+
+    #[test]
+    fn test_sync_query() {
+        let body = r#"{"resource": "meow"}"#;
+
+        let mut builder = Client::builder("secret");
+        builder.transport(MockTransport::new(200, body));
+
+        let client = builder.build().unwrap();
+        let mut runtime = Runtime::new().unwrap();
+        let expected = runtime.block_on(client.query("meow")).unwrap();
+
+        let client = mock_client(MockTransport::new(200, body), |_| ());
+
+        assert_eq!(expected, client.query("meow").unwrap());
+    }
+
+    #[test]
+    fn test_sync_query_all() {
+        let transport = MockTransport::new(200, r#"{"resource": ["meow", "purr"]}"#);
+        let client = mock_client(transport, |_| ());
+
+        let responses = client.query_all(vec!["meow", "purr"]).unwrap();
+        let resources: Vec<_> = responses.iter().map(|r| r.resource.as_str()).collect();
+
+        assert_eq!(vec![Some("meow"), Some("purr")], resources);
+    }
 }
@@ -0,0 +1,65 @@
+use crate::error::Error;
+use futures::{future, stream::Stream, Future};
+use http::header::{HeaderMap, CONTENT_LENGTH};
+use hyper::{client::connect::Connect, Body, Request, Response};
+
+/// The future returned from a [Transport](trait.Transport.html).
+pub type TransportFuture = Box<dyn Future<Item = Response<Body>, Error = Error> + Send + 'static>;
+
+/// Sends the HTTP requests of the client.
+///
+/// The client uses a `hyper` client over HTTPS by default. A custom transport
+/// can be set with
+/// [ClientBuilder::transport](struct.ClientBuilder.html#method.transport),
+/// e.g. for routing the requests through a proxy or for testing without a
+/// Fauna server.
+pub trait Transport: Send + Sync {
+    fn call(&self, request: Request<Body>) -> TransportFuture;
+}
+
+impl<C> Transport for hyper::Client<C>
+where
+    C: Connect + Sync + 'static,
+    C::Transport: 'static,
+    C::Future: 'static,
+{
+    fn call(&self, request: Request<Body>) -> TransportFuture {
+        Box::new(
+            self.request(request)
+                .map_err(|e| Error::ConnectionError(e.into())),
+        )
+    }
+}
+
+/// Reads the body, failing with `Error::ResponseTooLarge` as soon as it goes
+/// over the `limit`. A `Content-Length` over the limit fails before reading.
+pub(crate) fn read_body(
+    headers: &HeaderMap,
+    body: Body,
+    limit: Option<usize>,
+) -> impl Future<Item = Vec<u8>, Error = Error> {
+    let content_length = headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+
+    match (limit, content_length) {
+        (Some(limit), Some(length)) if length > limit as u64 => {
+            return future::Either::A(future::err(Error::ResponseTooLarge(limit)));
+        }
+        _ => (),
+    }
+
+    let reading = body.map_err(|e| Error::ConnectionError(e.into())).fold(
+        Vec::new(),
+        move |mut body, chunk| match limit {
+            Some(limit) if body.len() + chunk.len() > limit => Err(Error::ResponseTooLarge(limit)),
+            _ => {
+                body.extend_from_slice(&chunk);
+                Ok(body)
+            }
+        },
+    );
+
+    future::Either::B(reading)
+}
@@ -1,54 +1,120 @@
 use crate::client::Value;
 use failure::{self, Fail};
+use std::{borrow::Cow, fmt};
 
-#[derive(Debug, Fail)]
+#[derive(Debug)]
 pub enum Error {
-    #[fail(display = "Error connecting to the database: {}", _0)]
     ConnectionError(failure::Error),
-    #[fail(display = "Configuration error: {}", _0)]
     ConfigurationError(failure::Error),
-    #[fail(display = "Timed out")]
     TimeoutError,
-    #[fail(display = "Unknown error")]
     Other,
-    #[fail(display = "Unauthorized")]
     Unauthorized,
-    #[fail(display = "Server sent no response")]
     EmptyResponse,
-    #[fail(display = "Bad request: {}", _0)]
     BadRequest(FaunaErrors),
-    #[fail(display = "Not found: {}", _0)]
     NotFound(FaunaErrors),
-    #[fail(display = "Request data failure: {}", _0)]
     RequestDataFailure(&'static str),
-    #[fail(display = "Response data failure: {}", _0)]
-    ResponseDataFailure(&'static str),
-    #[fail(display = "Fauna error: {}", _0)]
+    ResponseDataFailure(Cow<'static, str>),
     DatabaseError(String),
-    #[fail(display = "Couldn't convert data: {}", _0)]
-    ConversionError(&'static str),
+    ConversionError(String),
+    BudgetExceeded {
+        kind: &'static str,
+        used: u64,
+        budget: u64,
+    },
+    TooManyItems(usize),
+    ResponseTooLarge(usize),
     #[cfg(feature = "sync_client")]
-    #[fail(display = "IO Error: {}", _0)]
     IoError(failure::Error),
 }
 
-#[derive(Debug, Deserialize, Fail)]
-#[fail(display = "Errors in the request data: [{:?}]", errors)]
+// The `Fail` impls are written by hand, because `failure_derive` expands them
+// inside named constants, which trips the `non_local_definitions` lint.
+impl Fail for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ConnectionError(e) => write!(f, "Error connecting to the database: {}", e),
+            Error::ConfigurationError(e) => write!(f, "Configuration error: {}", e),
+            Error::TimeoutError => write!(f, "Timed out"),
+            Error::Other => write!(f, "Unknown error"),
+            Error::Unauthorized => write!(f, "Unauthorized"),
+            Error::EmptyResponse => write!(f, "Server sent no response"),
+            Error::BadRequest(errors) => write!(f, "Bad request: {}", errors),
+            Error::NotFound(errors) => write!(f, "Not found: {}", errors),
+            Error::RequestDataFailure(e) => write!(f, "Request data failure: {}", e),
+            Error::ResponseDataFailure(e) => write!(f, "Response data failure: {}", e),
+            Error::DatabaseError(e) => write!(f, "Fauna error: {}", e),
+            Error::ConversionError(e) => write!(f, "Couldn't convert data: {}", e),
+            Error::BudgetExceeded { kind, used, budget } => write!(
+                f,
+                "Query used {} {} ops, over the budget of {}",
+                used, kind, budget
+            ),
+            Error::TooManyItems(max) => write!(f, "The set has more than {} elements", max),
+            Error::ResponseTooLarge(limit) => {
+                write!(f, "Response is over the limit of {} bytes", limit)
+            }
+            #[cfg(feature = "sync_client")]
+            Error::IoError(e) => write!(f, "IO Error: {}", e),
+        }
+    }
+}
+
+impl Error {
+    /// The message given to `Abort`, if the error is from an aborted
+    /// transaction.
+    pub fn abort_message(&self) -> Option<&str> {
+        match self {
+            Error::BadRequest(errors) => errors.abort_message(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
 pub struct FaunaErrors {
     pub errors: Vec<FaunaError>,
 }
 
-#[derive(Debug, Deserialize, Fail)]
-#[fail(
-    display = "{{position={:?},code={},description={}}}",
-    position, code, description
-)]
+impl FaunaErrors {
+    /// The message given to `Abort`, if one of the errors is from an aborted
+    /// transaction.
+    pub fn abort_message(&self) -> Option<&str> {
+        self.errors
+            .iter()
+            .find(|error| error.code == "transaction aborted")
+            .map(|error| error.description.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
 pub struct FaunaError {
     pub position: Vec<Value>,
     pub code: String,
     pub description: String,
 }
 
+impl Fail for FaunaErrors {}
+
+impl fmt::Display for FaunaErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Errors in the request data: [{:?}]", self.errors)
+    }
+}
+
+impl Fail for FaunaError {}
+
+impl fmt::Display for FaunaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{{position={:?},code={},description={}}}",
+            self.position, self.code, self.description
+        )
+    }
+}
+
 impl From<native_tls::Error> for Error {
     fn from(e: native_tls::Error) -> Self {
         Error::ConnectionError(e.into())
@@ -61,9 +127,54 @@ impl From<http::uri::InvalidUri> for Error {
     }
 }
 
+impl From<http::uri::InvalidUriParts> for Error {
+    fn from(e: http::uri::InvalidUriParts) -> Self {
+        Error::ConfigurationError(e.into())
+    }
+}
+
+impl From<http::header::InvalidHeaderValue> for Error {
+    fn from(e: http::header::InvalidHeaderValue) -> Self {
+        Error::ConfigurationError(e.into())
+    }
+}
+
 #[cfg(feature = "sync_client")]
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IoError(e.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display() {
+        let budget = Error::BudgetExceeded {
+            kind: "read",
+            used: 12,
+            budget: 10,
+        };
+
+        assert_eq!(
+            "Query used 12 read ops, over the budget of 10",
+            budget.to_string()
+        );
+        assert_eq!("Timed out", Error::TimeoutError.to_string());
+
+        let errors = FaunaErrors {
+            errors: vec![FaunaError {
+                position: Vec::new(),
+                code: String::from("invalid argument"),
+                description: String::from("Ref expected."),
+            }],
+        };
+
+        assert_eq!(
+            "Bad request: Errors in the request data: [[FaunaError { position: [], code: \"invalid argument\", description: \"Ref expected.\" }]]",
+            Error::BadRequest(errors).to_string()
+        );
+    }
+}
@@ -1,16 +1,31 @@
 //! A Fauna expression that is either a value or a function that evaluates to a value.
 mod array;
+#[cfg(feature = "v10")]
+mod module;
 mod number;
 mod object;
 mod permission;
 mod reference;
 mod set;
 
-use crate::{query::Query, serde::base64_bytes};
+use crate::{
+    query::{
+        basic::{Binding, If, Let, Var, COALESCE_VAR},
+        logical::Equals,
+        Query,
+    },
+    serde::base64_bytes,
+};
 use chrono::{DateTime, NaiveDate, Utc};
-use std::{borrow::Cow, fmt};
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashSet},
+    fmt,
+};
 
 pub use array::{Array, Bytes};
+#[cfg(feature = "v10")]
+pub use module::Module;
 pub use number::*;
 pub use object::Object;
 pub use permission::*;
@@ -83,6 +98,10 @@ pub enum AnnotatedExpr<'a> {
     /// specified, left to right. Objects evaluate to their contents:
     #[serde(rename = "object")]
     Object(Box<Object<'a>>),
+    /// A reference to an FQL v10 module.
+    #[cfg(feature = "v10")]
+    #[serde(rename = "@mod")]
+    Module(Module<'a>),
 }
 
 /// A representation of a FaunaDB Query Expression.
@@ -115,18 +134,21 @@ impl<'a> fmt::Display for Expr<'a> {
             Expr::Simple(SimpleExpr::Number(Number::UInt(i))) => write!(f, "{}", i),
             Expr::Simple(SimpleExpr::Boolean(b)) => write!(f, "{}", b),
             Expr::Simple(SimpleExpr::Null) => write!(f, "null"),
-            Expr::Simple(SimpleExpr::Array(v)) => {
-                let exprs: Vec<String> = v.0.iter().map(|e| format!("{}", e)).collect();
-                write!(f, "[{}]", exprs.join(","))
-            }
+            Expr::Simple(SimpleExpr::Array(v)) => write!(f, "{}", v),
             Expr::Simple(SimpleExpr::Object(o)) => write!(f, "{}", o),
             Expr::Annotated(AnnotatedExpr::Object(o)) => write!(f, "{}", o),
-            Expr::Annotated(AnnotatedExpr::Bytes(b)) => write!(f, "{}", base64::encode(&b.0)),
+            Expr::Annotated(AnnotatedExpr::Bytes(b)) => write!(
+                f,
+                "{}",
+                base64::display::Base64Display::with_config(&b.0, base64::STANDARD)
+            ),
             Expr::Annotated(AnnotatedExpr::Date(d)) => write!(f, "{}", d),
             Expr::Annotated(AnnotatedExpr::Ref(r)) => write!(f, "{}", r),
             Expr::Annotated(AnnotatedExpr::Set(s)) => write!(f, "{}", s),
             Expr::Annotated(AnnotatedExpr::Timestamp(ts)) => write!(f, "{}", ts),
             Expr::Annotated(AnnotatedExpr::Quote(q)) => write!(f, "Quote({:?})", q),
+            #[cfg(feature = "v10")]
+            Expr::Annotated(AnnotatedExpr::Module(m)) => write!(f, "{}", m),
             Expr::Query(query) => write!(f, "Query({:?})", query),
         }
     }
@@ -156,6 +178,45 @@ impl<'a> Expr<'a> {
         Expr::Simple(SimpleExpr::Null)
     }
 
+    /// The first of the expressions not evaluating to `null`, or `null` if
+    /// all of them do.
+    ///
+    /// Fauna has no coalescing function, so this builds a chain of `If`
+    /// conditionals. Every expression is bound in a `Let` so it's evaluated
+    /// only once, and the ones after the first non-null are not evaluated.
+    /// The binding uses the reserved variable name `_v_coalesce`.
+    ///
+    /// A `Select` on a missing path fails instead of evaluating to `null`, so
+    /// it needs a `null` default to fall through to the next expression.
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// let mut nickname = Select::new(vec!["data", "nickname"], Var::new("cat"));
+    /// nickname.default(Expr::null());
+    ///
+    /// let name = Expr::coalesce(vec![
+    ///     nickname,
+    ///     Select::new(vec!["data", "name"], Var::new("cat")),
+    /// ]);
+    /// ```
+    pub fn coalesce<E>(exprs: Vec<E>) -> Self
+    where
+        E: Into<Expr<'a>>,
+    {
+        let mut exprs = exprs.into_iter().rev().map(Into::into);
+        let last = exprs.next().unwrap_or_else(Expr::null);
+
+        exprs.fold(last, |rest, expr| {
+            let is_null = Equals::new(Var::new(COALESCE_VAR), Expr::null());
+            let choice = If::cond(is_null, rest, Var::new(COALESCE_VAR));
+
+            Expr::from(Let::bindings(
+                vec![Binding::new(COALESCE_VAR, expr)],
+                choice,
+            ))
+        })
+    }
+
     /// Quote the expression to prevent Fauna evalutating it.
     pub fn into_quoted(self) -> Self {
         Expr::Annotated(AnnotatedExpr::Quote(Box::new(self)))
@@ -165,6 +226,62 @@ impl<'a> Expr<'a> {
     pub fn as_quoted(&self) -> Self {
         self.clone().into_quoted()
     }
+
+    /// A key for caching the results of the expression, computed from its
+    /// serialized form with 64-bit FNV-1a.
+    ///
+    /// Object keys are always serialized in sorted order, so expressions built
+    /// in a different order but serializing the same give the same key. The
+    /// key is stable between processes and crate versions with an unchanged
+    /// serialization.
+    pub fn cache_key(&self) -> u64 {
+        let serialized = serde_json::to_vec(self).unwrap();
+
+        serialized.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+}
+
+/// Conversion into an [Expr](enum.Expr.html), accepted by
+/// [Object::insert](struct.Object.html#method.insert),
+/// [Array::push](struct.Array.html#method.push) and when building an `Array`
+/// from a `Vec`.
+///
+/// Implemented for everything converting `Into<Expr>`. Implement it for your
+/// own types to store them as structured data:
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// struct Money {
+///     cents: i64,
+///     currency: &'static str,
+/// }
+///
+/// impl<'a> IntoExpr<'a> for Money {
+///     fn into_expr(self) -> Expr<'a> {
+///         let mut object = Object::default();
+///         object.insert("cents", self.cents);
+///         object.insert("currency", self.currency);
+///
+///         Expr::from(object)
+///     }
+/// }
+///
+/// let mut data = Object::default();
+/// data.insert("price", Money { cents: 420, currency: "EUR" });
+/// ```
+pub trait IntoExpr<'a> {
+    fn into_expr(self) -> Expr<'a>;
+}
+
+impl<'a, T> IntoExpr<'a> for T
+where
+    T: Into<Expr<'a>>,
+{
+    fn into_expr(self) -> Expr<'a> {
+        self.into()
+    }
 }
 
 impl<'a, T> From<Option<T>> for Expr<'a>
@@ -203,6 +320,33 @@ impl<'a> From<Array<'a>> for Expr<'a> {
     }
 }
 
+impl<'a, E> From<&'a [E]> for Expr<'a>
+where
+    E: IntoExpr<'a> + Clone,
+{
+    fn from(s: &'a [E]) -> Expr<'a> {
+        Expr::from(Array::from(s))
+    }
+}
+
+impl<'a, E> From<BTreeSet<E>> for Expr<'a>
+where
+    E: IntoExpr<'a>,
+{
+    fn from(s: BTreeSet<E>) -> Expr<'a> {
+        Expr::from(Array::from(s))
+    }
+}
+
+impl<'a, E, S> From<HashSet<E, S>> for Expr<'a>
+where
+    E: IntoExpr<'a>,
+{
+    fn from(s: HashSet<E, S>) -> Expr<'a> {
+        Expr::from(Array::from(s))
+    }
+}
+
 impl<'a, Q> From<Q> for Expr<'a>
 where
     Q: Into<Query<'a>>,
@@ -224,6 +368,13 @@ impl<'a> From<Bytes<'a>> for Expr<'a> {
     }
 }
 
+#[cfg(feature = "v10")]
+impl<'a> From<Module<'a>> for Expr<'a> {
+    fn from(module: Module<'a>) -> Self {
+        Expr::Annotated(AnnotatedExpr::Module(module))
+    }
+}
+
 impl<'a> From<Ref<'a>> for Expr<'a> {
     fn from(r: Ref<'a>) -> Expr<'a> {
         Expr::Annotated(AnnotatedExpr::Ref(Box::new(r)))
@@ -260,6 +411,16 @@ impl<'a> From<f32> for Expr<'a> {
     }
 }
 
+/// A `Decimal` is stored as a string to keep it lossless, Fauna numbers being
+/// doubles. Read it back with `Value::as_decimal`. Convert it with
+/// `ToNumber` if doing arithmetic in Fauna, accepting the precision loss.
+#[cfg(feature = "decimal")]
+impl<'a> From<rust_decimal::Decimal> for Expr<'a> {
+    fn from(d: rust_decimal::Decimal) -> Self {
+        Expr::Simple(SimpleExpr::String(Cow::from(d.to_string())))
+    }
+}
+
 impl<'a> From<Number> for Expr<'a> {
     fn from(num: Number) -> Expr<'a> {
         Expr::Simple(SimpleExpr::Number(num))
@@ -268,9 +429,160 @@ impl<'a> From<Number> for Expr<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::prelude::*;
+    use crate::{prelude::*, test_utils::*};
     use chrono::{DateTime, NaiveDate, Utc};
     use serde_json::{self, json};
+    use std::collections::{BTreeSet, HashMap, HashSet};
+
+    #[test]
+    fn test_bytes_from_hex() {
+        let bytes = Bytes::from_hex("CAFE00").unwrap();
+
+        assert_eq!(&[0xca, 0xfe, 0x00][..], &*bytes.0);
+        assert_eq!("cafe00", bytes.to_hex());
+        assert_eq!(
+            json!({"@bytes": "yv4A"}),
+            serde_json::to_value(Expr::from(bytes)).unwrap()
+        );
+
+        assert!(Bytes::from_hex("").unwrap().0.is_empty());
+        assert!(Bytes::from_hex("caf").is_err());
+        assert!(Bytes::from_hex("+f").is_err());
+        assert!(Bytes::from_hex("zz").is_err());
+        assert!(Bytes::from_hex("é").is_err());
+    }
+
+    #[test]
+    fn test_coalesce_keeps_user_vars() {
+        let coalesce = Expr::coalesce(vec![Expr::null(), Expr::from(Var::new("coalesce"))]);
+
+        let expected = json!({
+            "let": {"_v_coalesce": null},
+            "in": {
+                "if": {"equals": [{"var": "_v_coalesce"}, null]},
+                "then": {"var": "coalesce"},
+                "else": {"var": "_v_coalesce"}
+            }
+        });
+
+        assert_eq!(expected, serde_json::to_value(coalesce).unwrap());
+    }
+
+    #[test]
+    fn test_coalesce() {
+        let coalesce = Expr::coalesce(vec![Expr::from(Var::new("nickname")), Expr::from("Musti")]);
+
+        let expected = json!({
+            "let": {"_v_coalesce": {"var": "nickname"}},
+            "in": {
+                "if": {"equals": [{"var": "_v_coalesce"}, null]},
+                "then": "Musti",
+                "else": {"var": "_v_coalesce"}
+            }
+        });
+
+        assert_eq!(expected, serde_json::to_value(coalesce).unwrap());
+        assert_eq!(
+            json!(null),
+            serde_json::to_value(Expr::coalesce(Vec::<Expr>::new())).unwrap()
+        );
+        assert_eq!(
+            json!(1),
+            serde_json::to_value(Expr::coalesce(vec![1])).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_coalesce_eval() {
+        let mut cat = Object::default();
+        cat.insert("name", "Musti");
+
+        let mut with_default = Select::new(vec!["nickname"], Expr::from(cat.clone()));
+        with_default.default(Expr::null());
+
+        let query = Expr::coalesce(vec![
+            Expr::null(),
+            Expr::from(with_default),
+            Expr::from(Select::new(vec!["name"], Expr::from(cat))),
+            Expr::from("Naukio"),
+        ]);
+
+        let response = CLIENT.query(query).unwrap();
+        assert_eq!(Some("Musti"), response.resource.as_str());
+
+        let response = CLIENT
+            .query(Expr::coalesce(vec![Expr::null(), Expr::null()]))
+            .unwrap();
+        assert!(response.resource.is_null());
+    }
+
+    #[test]
+    fn test_array_with_capacity() {
+        let mut array = Array::with_capacity(10_000);
+        let capacity = array.capacity();
+
+        assert!(capacity >= 10_000);
+
+        for i in 0..10_000 {
+            array.push(i);
+        }
+
+        assert_eq!(capacity, array.capacity());
+
+        array.reserve(5);
+        assert!(array.capacity() >= 10_005);
+
+        let serialized = serde_json::to_value(Expr::from(array)).unwrap();
+
+        assert_eq!(10_000, serialized.as_array().unwrap().len());
+        assert_eq!(json!(9_999), serialized[9_999]);
+    }
+
+    #[test]
+    fn test_slice_expr() {
+        fn names_expr(names: &[String]) -> Expr<'_> {
+            Expr::from(names)
+        }
+
+        let names = vec![String::from("Musti"), String::from("Naukio")];
+        let ages: &[u64] = &[7, 3];
+
+        assert_eq!(
+            json!(["Musti", "Naukio"]),
+            serde_json::to_value(names_expr(&names)).unwrap()
+        );
+
+        assert_eq!(
+            json!([7, 3]),
+            serde_json::to_value(Array::from(ages)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_btree_set_expr() {
+        let set: BTreeSet<&str> = vec!["foo", "bar", "foo"].into_iter().collect();
+
+        assert_eq!(
+            json!(["bar", "foo"]),
+            serde_json::to_value(Expr::from(set)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_hash_set_expr() {
+        let set: HashSet<i64> = vec![1, 2, 1].into_iter().collect();
+        let mut serialized: Vec<i64> = serde_json::to_value(Array::from(set))
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_i64().unwrap())
+            .collect();
+
+        serialized.sort();
+
+        assert_eq!(vec![1, 2], serialized);
+    }
 
     #[test]
     fn test_string_expr() {
@@ -296,6 +608,21 @@ mod tests {
         assert_eq!("4.12", serialized);
     }
 
+    #[test]
+    fn test_f32_and_f64_expr_equality() {
+        let float = serde_json::to_string(&Expr::from(1.5f32)).unwrap();
+        let double = serde_json::to_string(&Expr::from(1.5f64)).unwrap();
+
+        assert_eq!(float, double);
+    }
+
+    #[test]
+    fn test_f32_widens_to_double() {
+        assert_eq!(Number::from(4.12f64), Number::from(4.12f32));
+        assert!(Number::from(4.12f32).is_f64());
+        assert_eq!(Some(4.12), Value::from(4.12f32).as_f64());
+    }
+
     #[test]
     fn test_i8_expr() {
         let expr = Expr::from(4i8);
@@ -360,6 +687,41 @@ mod tests {
         assert_eq!("4", serialized);
     }
 
+    #[test]
+    fn test_u64_max_expr() {
+        let expr = Expr::from(u64::MAX);
+        let serialized = serde_json::to_string(&expr).unwrap();
+
+        assert_eq!("18446744073709551615", serialized);
+        assert!(Number::safe_u64(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_safe_integers() {
+        assert_eq!(
+            Number::UInt(MAX_SAFE_INTEGER),
+            Number::safe_u64(MAX_SAFE_INTEGER).unwrap()
+        );
+
+        assert!(Number::safe_u64(MAX_SAFE_INTEGER + 1).is_err());
+
+        let min = -(MAX_SAFE_INTEGER as i64);
+
+        assert_eq!(Number::Int(min), Number::safe_i64(min).unwrap());
+        assert!(Number::safe_i64(min - 1).is_err());
+        assert!(Number::safe_i64(i64::MIN).is_err());
+        assert!(Number::safe_i64(i64::MAX).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn test_decimal_expr() {
+        let decimal: rust_decimal::Decimal = "12345678901234567890.123456789".parse().unwrap();
+        let serialized = serde_json::to_value(Expr::from(decimal)).unwrap();
+
+        assert_eq!(json!("12345678901234567890.123456789"), serialized);
+    }
+
     #[test]
     fn test_bytes_expr() {
         let expr = Expr::from(Bytes::from(vec![0x1, 0x2, 0x3, 0x4]));
@@ -370,7 +732,7 @@ mod tests {
 
     #[test]
     fn test_date_expr() {
-        let expr = Expr::from(NaiveDate::from_ymd(2001, 5, 31));
+        let expr = Expr::from(NaiveDate::from_ymd_opt(2001, 5, 31).unwrap());
         let serialized = serde_json::to_string(&expr).unwrap();
 
         assert_eq!("{\"@date\":\"2001-05-31\"}", serialized)
@@ -403,6 +765,125 @@ mod tests {
         assert_eq!(expected, serialized)
     }
 
+    #[test]
+    fn test_native_refs_expr() {
+        let serialized = serde_json::to_value(Expr::from(Ref::database("cats"))).unwrap();
+
+        let expected = json!({
+            "@ref": {
+                "class": {"@ref": {"id": "databases"}},
+                "id": "cats"
+            }
+        });
+
+        assert_eq!(expected, serialized);
+
+        let serialized = serde_json::to_value(Expr::from(Ref::function("meow"))).unwrap();
+
+        let expected = json!({
+            "@ref": {
+                "class": {"@ref": {"id": "functions"}},
+                "id": "meow"
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_ref_round_trip() {
+        let mut instance = Ref::instance("1");
+        instance.set_class("cats");
+
+        for refer in [
+            Ref::database("cats"),
+            Ref::function("meow"),
+            Ref::class("cats"),
+            Ref::index("cats_by_name"),
+            instance,
+        ] {
+            let serialized = serde_json::to_string(&refer).unwrap();
+            let deserialized: Ref = serde_json::from_str(&serialized).unwrap();
+
+            assert_eq!(refer, deserialized);
+            assert_eq!(refer.to_string(), deserialized.to_string());
+        }
+
+        let from_fauna: Ref =
+            serde_json::from_value(json!({"id": "cats", "class": {"@ref": {"id": "databases"}}}))
+                .unwrap();
+
+        assert_eq!(Ref::database("cats"), from_fauna);
+        assert_eq!(
+            "Ref(id=meow,function=functions)",
+            Ref::function("meow").to_string()
+        );
+    }
+
+    #[test]
+    fn test_current_key_expr() {
+        let refer = Ref::current_key();
+        assert!(refer.is_current());
+        assert!(!Ref::instance("self").is_current());
+        assert_eq!("Ref(id=self,class=keys)", refer.to_string());
+
+        let serialized = serde_json::to_value(Expr::from(refer)).unwrap();
+
+        let expected = json!({
+            "@ref": {
+                "class": {"@ref": {"id": "keys"}},
+                "id": "self"
+            }
+        });
+
+        assert_eq!(expected, serialized);
+
+        let serialized = serde_json::to_value(Expr::from(Ref::current_token())).unwrap();
+
+        let expected = json!({
+            "@ref": {
+                "class": {"@ref": {"id": "tokens"}},
+                "id": "self"
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_current_key_eval() {
+        with_scoped_client(|client, _| {
+            let response = client.query(Get::instance(Ref::current_key())).unwrap();
+
+            assert_eq!(Some("admin"), response.resource["role"].as_str());
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "v10")]
+    fn test_module_expr() {
+        let expr = Expr::from(Module::collection());
+        let serialized = serde_json::to_value(&expr).unwrap();
+
+        assert_eq!(json!({"@mod": "Collection"}), serialized);
+        assert_eq!("Collection", expr.to_string());
+
+        let mut args = Object::default();
+        args.insert("coll", Module::new("Cats"));
+        args.insert("fun", Module::function());
+
+        let serialized = serde_json::to_value(Expr::from(args)).unwrap();
+
+        let expected = json!({
+            "object": {
+                "coll": {"@mod": "Cats"},
+                "fun": {"@mod": "Function"},
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_bool_expr() {
         let expr = Expr::from(true);
@@ -456,6 +937,167 @@ mod tests {
         assert_eq!("{\"object\":{\"foo\":\"bar\",\"lol\":false}}", serialized)
     }
 
+    #[test]
+    fn test_ref_array_expr() {
+        let refs = vec![Ref::instance("1"), Ref::instance("2")];
+
+        let expected = json!([{ "@ref": { "id": "1" } }, { "@ref": { "id": "2" } }]);
+        let serialized = serde_json::to_value(Expr::from(Array::from(refs.clone()))).unwrap();
+
+        assert_eq!(expected, serialized);
+
+        let mut object = Object::default();
+        object.insert_refs("friends", refs);
+
+        let serialized = serde_json::to_value(Expr::from(object)).unwrap();
+
+        assert_eq!(json!({ "object": { "friends": expected } }), serialized);
+    }
+
+    #[test]
+    fn test_object_insert_object() {
+        let mut cat = Object::default();
+
+        cat.insert("name", "Musti").insert_object("owner", |owner| {
+            owner
+                .insert("name", "Julius")
+                .insert_object("address", |address| {
+                    address.insert("city", "Berlin");
+                });
+        });
+
+        let expected = json!({
+            "object": {
+                "name": "Musti",
+                "owner": {
+                    "object": {
+                        "name": "Julius",
+                        "address": {"object": {"city": "Berlin"}}
+                    }
+                }
+            }
+        });
+
+        assert_eq!(expected, serde_json::to_value(Expr::from(cat)).unwrap());
+    }
+
+    #[test]
+    fn test_object_and_array_display() {
+        let mut nested = Object::default();
+        nested.insert("lives", 9);
+
+        let mut object = Object::default();
+        object.insert("name", "Musti");
+        object.insert("tags", Array::from(vec!["cat", "orange"]));
+        object.insert("stats", nested);
+
+        let expected = r#"{"name":"Musti","stats":{"lives":9},"tags":["cat","orange"]}"#;
+
+        assert_eq!(expected, object.to_string());
+        assert_eq!(expected, format!("{:?}", object));
+        assert_eq!(expected, Expr::from(object).to_string());
+
+        let mut quoted = Object::default();
+        quoted.insert("say \"meow\"", 1);
+
+        assert_eq!(r#"{"say \"meow\"":1}"#, quoted.to_string());
+
+        let array = Array::from(vec![Expr::from(1), Expr::null(), Expr::from(true)]);
+
+        assert_eq!("[1,null,true]", array.to_string());
+        assert_eq!("[1,null,true]", format!("{:?}", array));
+    }
+
+    #[test]
+    fn test_object_from_hash_map_is_deterministic() {
+        let keys = ["foo", "bar", "baz", "qux", "quux", "corge", "grault"];
+
+        let mut object = Object::default();
+        for (i, key) in keys.iter().copied().enumerate() {
+            object.insert(key, i as i64);
+        }
+
+        let expected = serde_json::to_string(&Expr::from(object)).unwrap();
+
+        for _ in 0..10 {
+            let map: HashMap<&str, i64> = keys
+                .iter()
+                .enumerate()
+                .map(|(i, key)| (*key, i as i64))
+                .collect();
+
+            let serialized = serde_json::to_string(&Expr::from(Object::from(map))).unwrap();
+
+            assert_eq!(expected, serialized);
+        }
+    }
+
+    #[test]
+    fn test_object_from_iter_sorts_keys() {
+        let object: Object = vec![("lol", false), ("foo", true)].into_iter().collect();
+        let serialized = serde_json::to_string(&Expr::from(object)).unwrap();
+
+        assert_eq!("{\"object\":{\"foo\":true,\"lol\":false}}", serialized)
+    }
+
+    struct Point {
+        lat: f64,
+        lng: f64,
+    }
+
+    impl<'a> IntoExpr<'a> for Point {
+        fn into_expr(self) -> Expr<'a> {
+            Expr::from(Array::from(vec![self.lat, self.lng]))
+        }
+    }
+
+    #[test]
+    fn test_custom_into_expr() {
+        let mut object = Object::default();
+        object.insert(
+            "location",
+            Point {
+                lat: 60.1,
+                lng: 24.9,
+            },
+        );
+
+        let mut array = Array::from(vec![Point { lat: 1.0, lng: 2.0 }]);
+        array.push(Point { lat: 3.0, lng: 4.0 });
+
+        assert_eq!(
+            json!({"object": {"location": [60.1, 24.9]}}),
+            serde_json::to_value(Expr::from(object)).unwrap()
+        );
+
+        assert_eq!(
+            json!([[1.0, 2.0], [3.0, 4.0]]),
+            serde_json::to_value(Expr::from(array)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let mut first = Object::default();
+        first.insert("name", "Musti");
+        first.insert("age", 7);
+
+        let mut second = Object::default();
+        second.insert("age", 7);
+        second.insert("name", "Musti");
+
+        let first = Expr::from(Create::new(Ref::class("cats"), first));
+        let second = Expr::from(Create::new(Ref::class("cats"), second));
+
+        assert_eq!(first.cache_key(), second.cache_key());
+        assert_ne!(first.cache_key(), Expr::from("Musti").cache_key());
+    }
+
+    #[test]
+    fn test_cache_key_is_stable() {
+        assert_eq!(0x07cc_7607_b494_9e25, Expr::from("").cache_key());
+    }
+
     #[test]
     fn test_set_expr() {
         let set = Set::matching(Ref::index("cats_age"), 8);
@@ -495,4 +1137,19 @@ mod tests {
 
         assert_eq!(expected, serialized);
     }
+
+    #[test]
+    fn test_timestamp_expr_keeps_nanoseconds() {
+        let dt_str = "2019-05-26T16:20:00.123456789Z";
+        let dt = DateTime::parse_from_rfc3339(dt_str)
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let expr = Expr::from(dt);
+        let serialized = serde_json::to_value(&expr).unwrap();
+
+        let expected = json!({ "@ts": dt_str });
+
+        assert_eq!(expected, serialized);
+    }
 }
@@ -1,30 +1,128 @@
-use super::Expr;
-use std::borrow::Cow;
+use super::{Expr, IntoExpr};
+use crate::error::Error;
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashSet},
+    fmt,
+};
 
-#[derive(Debug, Clone, Serialize)]
+/// An array of expressions.
+///
+/// Both `Display` and `Debug` render the array compactly in the same format as
+/// [Expr](enum.Expr.html), e.g. `[1,"foo"]`.
+#[derive(Clone, Serialize)]
 pub struct Array<'a>(pub Vec<Expr<'a>>);
 
+/// Binary data, sent to Fauna as base64.
+///
+/// The data can be borrowed with `Bytes::from(&data[..])` so large blobs are
+/// not copied, and it is encoded straight into the request payload.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Bytes<'a>(pub Cow<'a, [u8]>);
 
 impl<'a> Array<'a> {
+    /// An empty array with room for `capacity` expressions before
+    /// reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Array(Vec::with_capacity(capacity))
+    }
+
+    /// Reserves room for at least `additional` more expressions.
+    pub fn reserve(&mut self, additional: usize) -> &mut Self {
+        self.0.reserve(additional);
+        self
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
     pub fn reuse(self) -> Self {
         let reused = self.0.into_iter().map(|e| e.reuse()).collect();
         Array(reused)
     }
 
-    pub fn push(&mut self, e: impl Into<Expr<'a>>) -> &mut Self {
-        self.0.push(e.into());
+    pub fn push(&mut self, e: impl IntoExpr<'a>) -> &mut Self {
+        self.0.push(e.into_expr());
         self
     }
 }
 
 impl<'a, E> From<Vec<E>> for Array<'a>
 where
-    E: Into<Expr<'a>>,
+    E: IntoExpr<'a>,
 {
     fn from(a: Vec<E>) -> Self {
-        Array(a.into_iter().map(Into::into).collect())
+        Array(a.into_iter().map(IntoExpr::into_expr).collect())
+    }
+}
+
+/// The elements are cloned from the slice.
+impl<'a, E> From<&'a [E]> for Array<'a>
+where
+    E: IntoExpr<'a> + Clone,
+{
+    fn from(a: &'a [E]) -> Self {
+        Array(a.iter().cloned().map(IntoExpr::into_expr).collect())
+    }
+}
+
+/// The elements are in ascending order.
+impl<'a, E> From<BTreeSet<E>> for Array<'a>
+where
+    E: IntoExpr<'a>,
+{
+    fn from(a: BTreeSet<E>) -> Self {
+        Array(a.into_iter().map(IntoExpr::into_expr).collect())
+    }
+}
+
+/// The elements are in the iteration order of the set, which is arbitrary.
+impl<'a, E, S> From<HashSet<E, S>> for Array<'a>
+where
+    E: IntoExpr<'a>,
+{
+    fn from(a: HashSet<E, S>) -> Self {
+        Array(a.into_iter().map(IntoExpr::into_expr).collect())
+    }
+}
+
+impl<'a> fmt::Display for Array<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let exprs: Vec<String> = self.0.iter().map(|e| format!("{}", e)).collect();
+
+        write!(f, "[{}]", exprs.join(","))
+    }
+}
+
+impl<'a> fmt::Debug for Array<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<'a> Bytes<'a> {
+    /// Decodes the bytes from a hex string, e.g. `"cafe"`. Both lower and
+    /// upper case digits are accepted.
+    pub fn from_hex(hex: &str) -> crate::Result<Bytes<'static>> {
+        let digit = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+
+        let bytes = hex
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| match pair {
+                [high, low] => Some(digit(*high)? << 4 | digit(*low)?),
+                _ => None,
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| Error::ConversionError(String::from("Invalid hex string")))?;
+
+        Ok(Bytes(Cow::from(bytes)))
+    }
+
+    /// The bytes as a lower case hex string.
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{:02x}", byte)).collect()
     }
 }
 
@@ -0,0 +1,41 @@
+use std::{borrow::Cow, fmt};
+
+/// A reference to an FQL v10 module, such as the `Collection` module or a
+/// user-defined collection or function. Serialized in the tagged format as
+/// `{"@mod": "Collection"}`.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// let mut args = Object::default();
+/// args.insert("coll", Module::new("Cats"));
+///
+/// // used in the query as `coll.byName("Musti")`
+/// ```
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Module<'a>(Cow<'a, str>);
+
+impl<'a> Module<'a> {
+    pub fn new(name: impl Into<Cow<'a, str>>) -> Self {
+        Self(name.into())
+    }
+
+    /// The `Collection` module, for managing the collections.
+    pub fn collection() -> Self {
+        Self::new("Collection")
+    }
+
+    /// The `Function` module, for managing the user-defined functions.
+    pub fn function() -> Self {
+        Self::new("Function")
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'a> fmt::Display for Module<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -1,22 +1,77 @@
-use crate::expr::{Expr, SimpleExpr};
+use crate::{
+    error::Error,
+    expr::{Expr, SimpleExpr},
+};
 
+/// The largest integer up to which every integer is exactly representable as
+/// an `f64`, `2^53 - 1`.
+pub const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// A Fauna number.
+///
+/// Fauna has a single double precision type for real numbers, so an `f32` is
+/// widened to a `Number::Double` when converted, keeping the decimal value the
+/// `f32` represents: `Number::from(4.12f32) == Number::from(4.12f64)`. Values
+/// read from Fauna are always `Double`.
+///
+/// The `From` conversions of integers are sent as they are, but integers
+/// outside of `±MAX_SAFE_INTEGER` can lose precision when Fauna treats them as
+/// doubles, and a `u64` over `i64::MAX` does not fit Fauna's integers. Use
+/// [safe_u64](#method.safe_u64) or [safe_i64](#method.safe_i64) to fail on
+/// such values, e.g. for large ids.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[serde(untagged)]
 pub enum Number {
     UInt(u64),
     Int(i64),
     Double(f64),
+    /// Kept for compatibility, the `From` conversions never produce this
+    /// variant.
     Float(f32),
 }
 
 impl Number {
-    pub fn is_u64(&self) -> bool {
+    /// The integer as a `Number`, or an error if it is over
+    /// [MAX_SAFE_INTEGER](constant.MAX_SAFE_INTEGER.html).
+    pub fn safe_u64(u: u64) -> crate::Result<Self> {
+        if u > MAX_SAFE_INTEGER {
+            return Err(Error::ConversionError(String::from(
+                "Integer is too large to be represented exactly",
+            )));
+        }
+
+        Ok(Number::UInt(u))
+    }
+
+    /// The integer as a `Number`, or an error if it is outside of
+    /// `±MAX_SAFE_INTEGER`.
+    pub fn safe_i64(i: i64) -> crate::Result<Self> {
+        if i.unsigned_abs() > MAX_SAFE_INTEGER {
+            return Err(Error::ConversionError(String::from(
+                "Integer is too large to be represented exactly",
+            )));
+        }
+
+        Ok(Number::Int(i))
+    }
+
+    /// The number as a `Decimal`. A `Double` is converted from its shortest
+    /// decimal representation, so e.g. `0.1` gives exactly `0.1`, but any
+    /// precision already lost in the double stays lost.
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Option<rust_decimal::Decimal> {
         match self {
-            Number::UInt(_) => true,
-            _ => false,
+            Number::UInt(u) => Some(rust_decimal::Decimal::from(*u)),
+            Number::Int(i) => Some(rust_decimal::Decimal::from(*i)),
+            Number::Double(d) => d.to_string().parse().ok(),
+            Number::Float(f) => f.to_string().parse().ok(),
         }
     }
 
+    pub fn is_u64(&self) -> bool {
+        matches!(self, Number::UInt(_))
+    }
+
     pub fn as_u64(&self) -> Option<u64> {
         match self {
             Number::UInt(u) => Some(*u),
@@ -25,10 +80,7 @@ impl Number {
     }
 
     pub fn is_i64(&self) -> bool {
-        match self {
-            Number::Int(_) => true,
-            _ => false,
-        }
+        matches!(self, Number::Int(_))
     }
 
     pub fn as_i64(&self) -> Option<i64> {
@@ -39,10 +91,7 @@ impl Number {
     }
 
     pub fn is_f64(&self) -> bool {
-        match self {
-            Number::Double(_) => true,
-            _ => false,
-        }
+        matches!(self, Number::Double(_))
     }
 
     pub fn as_f64(&self) -> Option<f64> {
@@ -53,10 +102,7 @@ impl Number {
     }
 
     pub fn is_f32(&self) -> bool {
-        match self {
-            Number::Float(_) => true,
-            _ => false,
-        }
+        matches!(self, Number::Float(_))
     }
 
     pub fn as_f32(&self) -> Option<f32> {
@@ -76,8 +122,11 @@ impl From<f64> for Number {
     }
 }
 
-impl<'a> From<f32> for Number {
+impl From<f32> for Number {
     fn from(f: f32) -> Number {
-        Number::Float(f)
+        // Going through the shortest decimal representation prevents the
+        // binary widening from turning `4.12` into `4.119999885559082`.
+        let double = f.to_string().parse().unwrap_or_else(|_| f64::from(f));
+        Number::Double(double)
     }
 }
@@ -1,7 +1,21 @@
-use crate::expr::Expr;
-use std::{borrow::Cow, collections::BTreeMap, fmt};
+use crate::expr::{Array, Expr, IntoExpr, Ref};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt,
+    hash::BuildHasher,
+    iter::FromIterator,
+};
 
-#[derive(Debug, Serialize, Clone, Default)]
+/// An object of key-value pairs.
+///
+/// The keys are always kept sorted, so the same logical object serializes to
+/// the same bytes no matter in which order the pairs were added or from which
+/// collection it was converted.
+///
+/// Both `Display` and `Debug` render the object compactly in the same format
+/// as [Expr](enum.Expr.html), e.g. `{"name":"Musti"}`.
+#[derive(Serialize, Clone, Default)]
 pub struct Object<'a>(pub(crate) BTreeMap<Cow<'a, str>, Expr<'a>>);
 
 impl<'a> From<BTreeMap<Cow<'a, str>, Expr<'a>>> for Object<'a> {
@@ -10,15 +24,72 @@ impl<'a> From<BTreeMap<Cow<'a, str>, Expr<'a>>> for Object<'a> {
     }
 }
 
+impl<'a, K, V, S> From<HashMap<K, V, S>> for Object<'a>
+where
+    K: Into<Cow<'a, str>>,
+    V: IntoExpr<'a>,
+    S: BuildHasher,
+{
+    fn from(data: HashMap<K, V, S>) -> Self {
+        data.into_iter().collect()
+    }
+}
+
+impl<'a, K, V> FromIterator<(K, V)> for Object<'a>
+where
+    K: Into<Cow<'a, str>>,
+    V: IntoExpr<'a>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let data = iter
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into_expr()))
+            .collect();
+
+        Object(data)
+    }
+}
+
 impl<'a> Object<'a> {
     pub fn insert<E>(&mut self, key: &'a str, val: E) -> &mut Self
     where
-        E: Into<Expr<'a>>,
+        E: IntoExpr<'a>,
     {
-        self.0.insert(Cow::from(key), val.into());
+        self.0.insert(Cow::from(key), val.into_expr());
         self
     }
 
+    /// Inserts the refs as an array, e.g. for a field relating to other
+    /// instances.
+    pub fn insert_refs(&mut self, key: &'a str, refs: Vec<Ref<'a>>) -> &mut Self {
+        self.insert(key, Array::from(refs))
+    }
+
+    /// Inserts a nested object, populated by `f`.
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// let mut cat = Object::default();
+    ///
+    /// cat.insert("name", "Musti").insert_object("owner", |owner| {
+    ///     owner.insert("name", "Julius").insert_object("address", |address| {
+    ///         address.insert("city", "Berlin");
+    ///     });
+    /// });
+    /// ```
+    pub fn insert_object<F>(&mut self, key: &'a str, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Object<'a>),
+    {
+        let mut object = Object::default();
+        f(&mut object);
+
+        self.insert(key, object)
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
@@ -35,8 +106,21 @@ impl<'a> Object<'a> {
 
 impl<'a> fmt::Display for Object<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let pairs: Vec<String> = self.0.iter().map(|(k, v)| format!("{}:{}", k, v)).collect();
+        let pairs: Result<Vec<String>, fmt::Error> = self
+            .0
+            .iter()
+            .map(|(k, v)| {
+                let key = serde_json::to_string(k).map_err(|_| fmt::Error)?;
+                Ok(format!("{}:{}", key, v))
+            })
+            .collect();
 
-        write!(f, "{{{}}}", pairs.join(","))
+        write!(f, "{{{}}}", pairs?.join(","))
+    }
+}
+
+impl<'a> fmt::Debug for Object<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
     }
 }
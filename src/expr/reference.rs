@@ -1,6 +1,12 @@
 use std::{borrow::Cow, fmt};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// The id of the special refs pointing to the caller's own key or token.
+const SELF_ID: &str = "self";
+
+/// Where the ref points to. Fauna nests databases and functions under the
+/// same `class` key as the classes, so they're told apart by the id of the
+/// nested ref when deserializing.
+#[derive(Debug, Clone, Serialize, PartialEq)]
 enum RefLocation<'a> {
     #[serde(rename = "class")]
     Class {
@@ -24,6 +30,45 @@ enum RefLocation<'a> {
     },
 }
 
+#[derive(Deserialize)]
+struct NestedRef<'a> {
+    #[serde(rename = "@ref")]
+    location: Box<Ref<'a>>,
+}
+
+/// The ref as it is on the wire.
+#[derive(Deserialize)]
+struct RawRef<'a> {
+    id: Cow<'a, str>,
+    #[serde(default)]
+    class: Option<NestedRef<'a>>,
+    #[serde(default)]
+    index: Option<NestedRef<'a>>,
+}
+
+impl<'a> From<RawRef<'a>> for Ref<'a> {
+    fn from(raw: RawRef<'a>) -> Self {
+        let location = match (raw.class, raw.index) {
+            (_, Some(NestedRef { location })) => Some(RefLocation::Index { location }),
+            (Some(NestedRef { location }), None) => {
+                let native = location.location.is_none();
+
+                Some(match location.id.as_ref() {
+                    "databases" if native => RefLocation::Database { location },
+                    "functions" if native => RefLocation::Function { location },
+                    _ => RefLocation::Class { location },
+                })
+            }
+            (None, None) => None,
+        };
+
+        Self {
+            id: raw.id,
+            location,
+        }
+    }
+}
+
 impl<'a> RefLocation<'a> {
     fn path(&self) -> String {
         match self {
@@ -36,6 +81,7 @@ impl<'a> RefLocation<'a> {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(from = "RawRef")]
 /// Denotes a resource ref.
 pub struct Ref<'a> {
     pub id: Cow<'a, str>,
@@ -53,7 +99,7 @@ impl<'a> fmt::Display for Ref<'a> {
                 write!(f, "Ref(id={},index={})", self.id, location.path())
             }
             Some(RefLocation::Function { ref location }) => {
-                write!(f, "Ref(id={},class={})", self.id, location.path())
+                write!(f, "Ref(id={},function={})", self.id, location.path())
             }
             Some(RefLocation::Database { ref location }) => {
                 write!(f, "Ref(id={},database={})", self.id, location.path())
@@ -127,6 +173,30 @@ impl<'a> Ref<'a> {
         }
     }
 
+    /// A ref to the key used for the current query, `Ref(Keys(), "self")`.
+    pub fn current_key() -> Self {
+        Self::current("keys")
+    }
+
+    /// A ref to the token used for the current query, `Ref(Tokens(), "self")`.
+    pub fn current_token() -> Self {
+        Self::current("tokens")
+    }
+
+    /// True, if the ref is one of the special `self` refs.
+    pub fn is_current(&self) -> bool {
+        self.id == SELF_ID && self.location.is_some()
+    }
+
+    fn current(class: &'static str) -> Self {
+        Self {
+            id: Cow::from(SELF_ID),
+            location: Some(RefLocation::Class {
+                location: Box::new(Self::instance(class)),
+            }),
+        }
+    }
+
     /// Set the class for the singleton ref.
     pub fn set_class<S>(&mut self, id: S) -> &mut Self
     where
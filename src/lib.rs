@@ -6,6 +6,9 @@
 //! Additionally the crate holds a `SyncClient` wrapper for synchronous
 //! execution, enabled with the `sync_client` feature flag.
 //!
+//! The `decimal` feature flag adds lossless `rust_decimal::Decimal` support,
+//! storing decimals as strings in Fauna.
+//!
 //! Most of the type checks are handled in Fauna and the functions accept
 //! anything that can be converted to the [Expr](expr/struct.Expr.html) enum,
 //! allowing the usage of different Fauna types in a more dynamic manner.
@@ -56,6 +59,31 @@
 //!     }
 //! }
 //! ```
+//!
+//! ## Setting up a new database:
+//!
+//! A new account has only an admin key, which can create databases but is not
+//! meant for querying them. `bootstrap` creates a database with a server key
+//! and returns a client using the key:
+//!
+//! ```no_run
+//! use faunadb::prelude::*;
+//!
+//! fn main() {
+//!     let admin = Client::builder("my_admin_secret").build_sync().unwrap();
+//!     let client = admin.bootstrap("my_app").unwrap();
+//!
+//!     client
+//!         .query(CreateClass::new(ClassParams::new("cats")))
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! The secret of the key can not be read later. To store it, create the
+//! database with
+//! [create_database_with_key](client/struct.Client.html#method.create_database_with_key)
+//! and the client with
+//! [with_secret](client/struct.Client.html#method.with_secret) instead.
 #[macro_use]
 extern crate serde_derive;
 
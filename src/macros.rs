@@ -67,3 +67,80 @@ macro_rules! uint_expr {
         )*
     );
 }
+
+/// Implements `Display` and `FromStr` for a fieldless enum with the given
+/// strings, which should match the serde renames of the variants.
+#[macro_export]
+macro_rules! string_enum {
+    ($kind:ident, $error:expr, { $($variant:ident => $name:literal),* $(,)* }) => (
+        impl std::fmt::Display for $kind {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                let name = match self {
+                    $($kind::$variant => $name,)*
+                };
+
+                f.write_str(name)
+            }
+        }
+
+        impl std::str::FromStr for $kind {
+            type Err = $crate::error::Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($name => Ok($kind::$variant),)*
+                    _ => Err($crate::error::Error::ConversionError(String::from($error))),
+                }
+            }
+        }
+    );
+}
+
+/// Adds a `try_new` for a variadic function, failing if there are no
+/// arguments instead of sending a query Fauna rejects with "Non-empty array
+/// expected". The functions taking an array in `new` are listed after
+/// `array:`, the ones built `From` an iterator after `iter:`.
+#[macro_export]
+macro_rules! non_empty {
+    (array: $($kind:ident),*) => (
+        $(
+            $crate::non_empty!(
+                @try_new $kind,
+                "Like `new` with the arguments in an array, but returns an error if \
+                 there are no arguments.",
+                args => Self::new($crate::expr::Array(args))
+            );
+        )*
+    );
+    (iter: $($kind:ident),*) => (
+        $(
+            $crate::non_empty!(
+                @try_new $kind,
+                "Like `from`, but returns an error if there are no arguments.",
+                args => Self::from(args)
+            );
+        )*
+    );
+    (@try_new $kind:ident, $doc:expr, $args:ident => $build:expr) => (
+        impl<'a> $kind<'a> {
+            #[doc = $doc]
+            pub fn try_new<I, E>($args: I) -> $crate::Result<Self>
+            where
+                I: IntoIterator<Item = E>,
+                E: Into<$crate::expr::Expr<'a>>,
+            {
+                let $args: Vec<$crate::expr::Expr<'a>> =
+                    $args.into_iter().map(Into::into).collect();
+
+                if $args.is_empty() {
+                    return Err($crate::error::Error::RequestDataFailure(concat!(
+                        stringify!($kind),
+                        " requires at least one argument"
+                    )));
+                }
+
+                Ok($build)
+            }
+        }
+    );
+}
@@ -2,7 +2,7 @@ pub use crate::{
     client::*,
     expr::*,
     query::{
-        auth::*, basic::*, collection::*, conversion::*, datetime::*, logical::*, math::*, misc::*,
-        read::*, set::*, string::*, write::*, Query,
+        aggregate::*, auth::*, basic::*, collection::*, conversion::*, datetime::*, logical::*,
+        math::*, misc::*, object::*, read::*, set::*, string::*, write::*, Query,
     },
 };
@@ -1,4 +1,5 @@
 //! A special case of an expression that needs to be evaluated to a value.
+pub mod aggregate;
 pub mod auth;
 pub mod basic;
 pub mod collection;
@@ -7,6 +8,7 @@ pub mod datetime;
 pub mod logical;
 pub mod math;
 pub mod misc;
+pub mod object;
 pub mod read;
 pub mod set;
 pub mod string;
@@ -15,29 +17,40 @@ pub mod write;
 #[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 pub enum Query<'a> {
+    Count(aggregate::Count<'a>),
+    Mean(aggregate::Mean<'a>),
+    Sum(aggregate::Sum<'a>),
+
     Difference(set::Difference<'a>),
     Distinct(set::Distinct<'a>),
     Intersection(set::Intersection<'a>),
     Join(set::Join<'a>),
     Match(set::Match<'a>),
+    Range(set::Range<'a>),
     Union(set::Union<'a>),
 
     Date(datetime::Date<'a>),
     Epoch(datetime::Epoch<'a>),
+    Now(datetime::Now<'a>),
     Time(datetime::Time<'a>),
 
     CaseFold(string::CaseFold<'a>),
     Concat(string::Concat<'a>),
+    EndsWith(string::EndsWith<'a>),
     FindStr(string::FindStr<'a>),
     FindStrRegex(string::FindStrRegex<'a>),
+    Format(string::Format<'a>),
     LTrim(string::LTrim<'a>),
     Length(string::Length<'a>),
     LowerCase(string::LowerCase<'a>),
+    NGram(string::NGram<'a>),
     RTrim(string::RTrim<'a>),
+    RegexEscape(string::RegexEscape<'a>),
     Repeat(string::Repeat<'a>),
     ReplaceStr(string::ReplaceStr<'a>),
     ReplaceStrRegex(string::ReplaceStrRegex<'a>),
     Space(string::Space<'a>),
+    StartsWith(string::StartsWith<'a>),
     SubString(string::SubString<'a>),
     TitleCase(string::TitleCase<'a>),
     Trim(string::Trim<'a>),
@@ -49,8 +62,12 @@ pub enum Query<'a> {
     Login(auth::Login<'a>),
     Logout(auth::Logout<'a>),
 
+    ToArray(conversion::ToArray<'a>),
     ToDate(conversion::ToDate<'a>),
+    ToDouble(conversion::ToDouble<'a>),
+    ToInteger(conversion::ToInteger<'a>),
     ToNumber(conversion::ToNumber<'a>),
+    ToObject(conversion::ToObject<'a>),
     ToString(conversion::ToString<'a>),
     ToTime(conversion::ToTime<'a>),
 
@@ -70,6 +87,8 @@ pub enum Query<'a> {
     IsNonEmpty(collection::IsNonEmpty<'a>),
     Map(collection::Map<'a>),
     Prepend(collection::Prepend<'a>),
+    Reduce(collection::Reduce<'a>),
+    Reverse(collection::Reverse<'a>),
     Take(collection::Take<'a>),
 
     And(logical::And<'a>),
@@ -80,6 +99,9 @@ pub enum Query<'a> {
     Gt(logical::Gt<'a>),
     Gte(logical::Gte<'a>),
     Contains(logical::Contains<'a>),
+    ContainsField(logical::ContainsField<'a>),
+    ContainsPath(logical::ContainsPath<'a>),
+    ContainsValue(logical::ContainsValue<'a>),
     Equals(logical::Equals<'a>),
     Exists(logical::Exists<'a>),
 
@@ -146,4 +168,6 @@ pub enum Query<'a> {
     Index(misc::Index<'a>),
     Indexes(misc::Indexes<'a>),
     NewId(misc::NewId<'a>),
+
+    Merge(object::Merge<'a>),
 }
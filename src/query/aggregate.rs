@@ -0,0 +1,92 @@
+//! Aggregate functions
+use crate::{expr::Expr, query::Query};
+
+query![Count, Mean, Sum];
+
+/// The `Count` function returns the number of elements in an array or a set.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/aggregate/count)
+#[derive(Serialize, Debug, Clone)]
+pub struct Count<'a> {
+    count: Expr<'a>,
+}
+
+impl<'a> Count<'a> {
+    pub fn new(collection: impl Into<Expr<'a>>) -> Self {
+        Self {
+            count: collection.into(),
+        }
+    }
+}
+
+/// The `Mean` function returns the average of the numbers in an array or a
+/// set.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/aggregate/mean)
+#[derive(Serialize, Debug, Clone)]
+pub struct Mean<'a> {
+    mean: Expr<'a>,
+}
+
+impl<'a> Mean<'a> {
+    pub fn new(collection: impl Into<Expr<'a>>) -> Self {
+        Self {
+            mean: collection.into(),
+        }
+    }
+}
+
+/// The `Sum` function returns the sum of the numbers in an array or a set.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/aggregate/sum)
+#[derive(Serialize, Debug, Clone)]
+pub struct Sum<'a> {
+    sum: Expr<'a>,
+}
+
+impl<'a> Sum<'a> {
+    pub fn new(collection: impl Into<Expr<'a>>) -> Self {
+        Self {
+            sum: collection.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use serde_json::{self, json};
+
+    #[test]
+    fn test_count() {
+        let fun = Count::new(Array::from(vec![1, 2, 3]));
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(json!({"count": [1, 2, 3]}), serialized);
+    }
+
+    #[test]
+    fn test_mean() {
+        let fun = Mean::new(Array::from(vec![1, 2, 3]));
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(json!({"mean": [1, 2, 3]}), serialized);
+    }
+
+    #[test]
+    fn test_sum() {
+        let fun = Sum::new(Array::from(vec![1, 2, 3]));
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(json!({"sum": [1, 2, 3]}), serialized);
+    }
+}
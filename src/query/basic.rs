@@ -1,10 +1,50 @@
 //! Basic functions
 use crate::{
-    expr::{Expr, Ref},
-    query::Query,
+    error::Error,
+    expr::{Array, Expr, Ref, SimpleExpr},
+    query::{write::FunctionParams, Query},
 };
 use chrono::{DateTime, Utc};
-use std::{borrow::Cow, collections::BTreeMap};
+use serde::{ser::SerializeMap, Serializer};
+use std::{borrow::Cow, cell::Cell};
+
+/// The prefix of the variable names generated by the crate. User variables
+/// starting with it may be shadowed by the generated ones.
+pub(crate) const GENERATED_VAR_PREFIX: &str = "_v";
+
+/// The variable the instance is bound to in a
+/// [Projection](../read/struct.Projection.html).
+pub(crate) const PROJECTION_VAR: &str = "_v_doc";
+
+/// The variable the selected value is bound to in
+/// [Select::default_if_null](../read/struct.Select.html#method.default_if_null).
+pub(crate) const SELECTED_VAR: &str = "_v_selected";
+
+/// The variable the expressions are bound to in
+/// [Expr::coalesce](../../expr/enum.Expr.html#method.coalesce).
+pub(crate) const COALESCE_VAR: &str = "_v_coalesce";
+
+thread_local! {
+    /// How many `Lambda::with_var` calls the current one is nested in.
+    static LAMBDA_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Keeps the lambda nesting depth for the duration of a `with_var` call,
+/// restoring it even if building the body panics.
+struct DepthGuard(usize);
+
+impl DepthGuard {
+    fn enter() -> Self {
+        let depth = LAMBDA_DEPTH.with(|depth| depth.replace(depth.get() + 1));
+        DepthGuard(depth)
+    }
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        LAMBDA_DEPTH.with(|depth| depth.set(self.0));
+    }
+}
 
 // Implements From<fun> for Query
 query![At, Call, If, Do, Let, Var, Lambda];
@@ -62,6 +102,30 @@ impl<'a> Call<'a> {
             arguments: arguments.into(),
         }
     }
+
+    /// Calls the function defined with the `params`, checking the number of
+    /// `arguments` against the [arity](struct.Lambda.html#method.arity) of its
+    /// body. Fails if the numbers don't match, and skips the check if the
+    /// arity is not known.
+    pub fn checked<E>(params: &FunctionParams<'a>, arguments: Vec<E>) -> crate::Result<Self>
+    where
+        E: Into<Expr<'a>>,
+    {
+        match params.arity() {
+            Some(arity) if arity != arguments.len() => Err(Error::RequestDataFailure(
+                "The number of arguments does not match the function",
+            )),
+            _ => Ok(Self::new(
+                Ref::function(params.name().to_string()),
+                Array::from(
+                    arguments
+                        .into_iter()
+                        .map(Into::into)
+                        .collect::<Vec<Expr<'a>>>(),
+                ),
+            )),
+        }
+    }
 }
 
 /// The `If` function evaluates and returns `if_true` or `if_false` depending on
@@ -165,6 +229,38 @@ impl<'a> Lambda<'a> {
             expr: expr.into(),
         }
     }
+
+    /// A `Lambda` with a single parameter, given to `f` as a `Var`. The
+    /// parameter is named by the nesting depth of the lambda, `_v0` for the
+    /// outermost, `_v1` for the one inside it and so on. Nested lambdas don't
+    /// shadow each other, and the same query always serializes the same.
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// let over_two = Lambda::with_var(|x| Gt::new(x, 2));
+    /// ```
+    pub fn with_var<F, E>(f: F) -> Self
+    where
+        F: FnOnce(Var<'a>) -> E,
+        E: Into<Expr<'a>>,
+    {
+        let guard = DepthGuard::enter();
+        let name = format!("{}{}", GENERATED_VAR_PREFIX, guard.0);
+        let expr = f(Var::new(name.clone()));
+        drop(guard);
+
+        Self::new(name, expr)
+    }
+
+    /// The number of arguments the lambda takes, if the parameters are an
+    /// array of names. A lambda with a single name takes the arguments as
+    /// they are, so it has no fixed arity.
+    pub fn arity(&self) -> Option<usize> {
+        match self.params {
+            Expr::Simple(SimpleExpr::Array(ref params)) => Some(params.0.len()),
+            _ => None,
+        }
+    }
 }
 
 /// The `Let` function binds one or more variables to a single value or
@@ -176,16 +272,32 @@ impl<'a> Lambda<'a> {
 /// via the in parameter. The value of a variable can be referenced with
 /// Var(varname) syntax.
 ///
+/// The bindings are serialized in the order they were given, so a binding can
+/// always refer to the ones defined before it.
+///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/basic/let).
 #[derive(Debug, Clone, Serialize)]
 pub struct Let<'a> {
-    #[serde(rename = "let")]
-    bindings: BTreeMap<Cow<'a, str>, Expr<'a>>,
+    #[serde(rename = "let", serialize_with = "serialize_bindings")]
+    bindings: Vec<Binding<'a>>,
     #[serde(rename = "in")]
     in_expr: Expr<'a>,
 }
 
+fn serialize_bindings<S>(bindings: &[Binding], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut map = serializer.serialize_map(Some(bindings.len()))?;
+
+    for Binding(variable, expr) in bindings {
+        map.serialize_entry(variable, expr)?;
+    }
+
+    map.end()
+}
+
 /// A single binding to be used in a `Let` query.
 #[derive(Debug, Clone, Serialize)]
 pub struct Binding<'a>(Cow<'a, str>, Expr<'a>);
@@ -202,20 +314,90 @@ impl<'a> Binding<'a> {
 
 impl<'a> Let<'a> {
     /// Set bindings to be available in the given `Expr`.
+    ///
+    /// In debug builds, panics if the same variable is bound more than once.
+    /// See [try_bindings](#method.try_bindings) for a checked version.
     pub fn bindings<B, E>(bindings: B, in_expr: E) -> Self
     where
         B: IntoIterator<Item = Binding<'a>>,
         E: Into<Expr<'a>>,
     {
-        let bindings = bindings
-            .into_iter()
-            .map(|binding| (binding.0, binding.1))
-            .collect();
+        let bindings: Vec<Binding<'a>> = bindings.into_iter().collect();
+
+        debug_assert!(
+            !has_duplicates(&bindings),
+            "The same variable is bound more than once in a Let"
+        );
 
         let in_expr = in_expr.into();
 
         Self { bindings, in_expr }
     }
+
+    /// Set bindings to be available in the given `Expr`, returning an error if
+    /// the same variable is bound more than once.
+    pub fn try_bindings<B, E>(bindings: B, in_expr: E) -> crate::Result<Self>
+    where
+        B: IntoIterator<Item = Binding<'a>>,
+        E: Into<Expr<'a>>,
+    {
+        let bindings: Vec<Binding<'a>> = bindings.into_iter().collect();
+
+        if has_duplicates(&bindings) {
+            return Err(Error::RequestDataFailure(
+                "The same variable is bound more than once in a Let",
+            ));
+        }
+
+        let in_expr = in_expr.into();
+
+        Ok(Self { bindings, in_expr })
+    }
+}
+
+fn has_duplicates(bindings: &[Binding]) -> bool {
+    bindings
+        .iter()
+        .enumerate()
+        .any(|(i, Binding(variable, _))| bindings[..i].iter().any(|b| &b.0 == variable))
+}
+
+/// A builder for accumulating [Let](struct.Let.html) bindings one at a time.
+///
+/// Bindings are kept in the order they were added, so every binding can use
+/// the variables bound before it.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// let query = QueryBuilder::new()
+///     .bind("a", 1)
+///     .bind("b", Add::new(Array::from(vec![Var::new("a"), Var::new("a")])))
+///     .returning(Var::new("b"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct QueryBuilder<'a> {
+    bindings: Vec<Binding<'a>>,
+}
+
+impl<'a> QueryBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind the result of `expr` to `variable`.
+    pub fn bind<V, E>(mut self, variable: V, expr: E) -> Self
+    where
+        V: Into<Cow<'a, str>>,
+        E: Into<Expr<'a>>,
+    {
+        self.bindings.push(Binding::new(variable, expr));
+        self
+    }
+
+    /// Finish the `Let` with the expression evaluated using the bindings.
+    pub fn returning(self, in_expr: impl Into<Expr<'a>>) -> Expr<'a> {
+        Expr::from(Let::bindings(self.bindings, in_expr))
+    }
 }
 
 /// Evaluate and return the value stored in a named variable.
@@ -223,6 +405,11 @@ impl<'a> Let<'a> {
 /// The `Var` statement can only be used inside other statements, such
 /// as [Let](struct.Let.html) or [Lambda](struct.Lambda.html).
 ///
+/// The names starting with `_v` are reserved for the variables the crate
+/// generates, e.g. in [Lambda::with_var](struct.Lambda.html#method.with_var)
+/// and [Expr::coalesce](../../expr/enum.Expr.html#method.coalesce), and may
+/// be shadowed by them.
+///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/basic/var)
 #[derive(Debug, Serialize, Clone)]
@@ -242,6 +429,7 @@ impl<'a> Var<'a> {
 #[cfg(test)]
 mod tests {
     use crate::{
+        error::Error,
         prelude::*,
         query::{misc::Classes, read::Get, write::Delete},
     };
@@ -250,7 +438,7 @@ mod tests {
 
     #[test]
     fn test_at() {
-        let fun = At::new(Utc.timestamp(60, 0), Classes::all());
+        let fun = At::new(Utc.timestamp_opt(60, 0).unwrap(), Classes::all());
         let query = Query::from(fun);
         let serialized = serde_json::to_value(&query).unwrap();
 
@@ -313,6 +501,75 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_let_binding_order() {
+        let let_var = Let::bindings(
+            vec![Binding::new("b", 1), Binding::new("a", Var::new("b"))],
+            Var::new("a"),
+        );
+
+        let serialized = serde_json::to_string(&Query::from(let_var)).unwrap();
+
+        assert_eq!(
+            r#"{"let":{"b":1,"a":{"var":"b"}},"in":{"var":"a"}}"#,
+            serialized
+        );
+    }
+
+    #[test]
+    fn test_let_try_bindings_duplicate() {
+        let let_var = Let::try_bindings(
+            vec![
+                Binding::new("cat", "Musti"),
+                Binding::new("dog", "Rex"),
+                Binding::new("cat", "Naukio"),
+            ],
+            Var::new("cat"),
+        );
+
+        assert!(let_var.is_err());
+    }
+
+    #[test]
+    fn test_let_try_bindings() {
+        let let_var = Let::try_bindings(
+            vec![Binding::new("cat", "Musti"), Binding::new("dog", "Rex")],
+            Var::new("cat"),
+        );
+
+        assert!(let_var.is_ok());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "bound more than once")]
+    fn test_let_bindings_duplicate_panics_in_debug() {
+        Let::bindings(
+            vec![Binding::new("cat", "Musti"), Binding::new("cat", "Naukio")],
+            Var::new("cat"),
+        );
+    }
+
+    #[test]
+    fn test_query_builder() {
+        let query = QueryBuilder::new()
+            .bind("cat", "Musti")
+            .bind(
+                "greeting",
+                Concat::new(Array::from(vec!["Hi", "Musti"]), " "),
+            )
+            .returning(Var::new("greeting"));
+
+        let serialized = serde_json::to_string(&query).unwrap();
+
+        let expected = concat!(
+            r#"{"let":{"cat":"Musti","greeting":{"concat":["Hi","Musti"],"separator":" "}},"#,
+            r#""in":{"var":"greeting"}}"#,
+        );
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_lambda() {
         let lambda = Lambda::new("cat", Var::new("cat"));
@@ -327,6 +584,90 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_lambda_with_var() {
+        let lambda = Lambda::with_var(|x| Gt::new(x, 2));
+        let serialized = serde_json::to_value(Query::from(lambda)).unwrap();
+
+        let name = serialized["lambda"].as_str().unwrap().to_string();
+        let manual = Lambda::new(name.clone(), Gt::new(Var::new(name), 2));
+
+        assert_eq!(
+            serde_json::to_value(Query::from(manual)).unwrap(),
+            serialized
+        );
+    }
+
+    #[test]
+    fn test_nested_lambda_with_var() {
+        let lambda = Lambda::with_var(|x| Lambda::with_var(|y| Gt::new(x, y)));
+        let serialized = serde_json::to_value(Query::from(lambda)).unwrap();
+
+        let outer = &serialized["lambda"];
+        let inner = &serialized["expr"]["lambda"];
+
+        assert_ne!(outer, inner);
+        assert_eq!(outer, &serialized["expr"]["expr"]["gt"][0]["var"]);
+        assert_eq!(inner, &serialized["expr"]["expr"]["gt"][1]["var"]);
+    }
+
+    #[test]
+    fn test_lambda_with_var_is_deterministic() {
+        let build = || {
+            let query = Map::new(
+                Array::from(vec![1, 2]),
+                Lambda::with_var(|x| Lambda::with_var(|y| Add::new(Array::from(vec![x, y])))),
+            );
+
+            serde_json::to_value(Query::from(query)).unwrap()
+        };
+
+        let first = build();
+
+        assert_eq!(first, build());
+        assert_eq!(json!("_v0"), first["map"]["lambda"]);
+        assert_eq!(json!("_v1"), first["map"]["expr"]["lambda"]);
+
+        let sibling = Lambda::with_var(|x| x);
+        let serialized = serde_json::to_value(Query::from(sibling)).unwrap();
+
+        assert_eq!(json!("_v0"), serialized["lambda"]);
+    }
+
+    #[test]
+    fn test_lambda_arity() {
+        let pair = Lambda::new(Array::from(vec!["x", "y"]), Var::new("x"));
+        let single = Lambda::new("x", Var::new("x"));
+
+        assert_eq!(Some(2), pair.arity());
+        assert_eq!(None, single.arity());
+    }
+
+    #[test]
+    fn test_checked_call() {
+        let params = FunctionParams::new(
+            "add",
+            Lambda::new(
+                Array::from(vec!["x", "y"]),
+                Add::new(Array::from(vec![Var::new("x"), Var::new("y")])),
+            ),
+        );
+
+        let fun = Call::checked(&params, vec![1, 2]).unwrap();
+        let serialized = serde_json::to_value(Query::from(fun)).unwrap();
+
+        assert_eq!(json!([1, 2]), serialized["arguments"]);
+        assert_eq!(json!("add"), serialized["call"]["@ref"]["id"]);
+
+        match Call::checked(&params, vec![1]) {
+            Err(Error::RequestDataFailure(_)) => (),
+            _ => panic!("Expected an arity mismatch"),
+        }
+
+        let params = FunctionParams::new("id", Lambda::new("x", Var::new("x")));
+        assert!(Call::checked(&params, vec![1, 2, 3]).is_ok());
+    }
+
     #[test]
     fn test_call() {
         let fun = Call::new(Ref::function("double"), 5);
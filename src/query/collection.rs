@@ -4,7 +4,7 @@ use crate::{
     query::{basic::Lambda, Query},
 };
 
-query![Append, Drop, Filter, Foreach, IsEmpty, IsNonEmpty, Map, Prepend, Take];
+query![Append, Drop, Filter, Foreach, IsEmpty, IsNonEmpty, Map, Prepend, Reduce, Reverse, Take];
 
 /// The `Append` function creates a new array that is the result of combining the
 /// base Array followed by the `elems`.
@@ -193,6 +193,57 @@ impl<'a> Prepend<'a> {
     }
 }
 
+/// The `Reduce` function applies a [Lambda](../basic/struct.Lambda.html) to
+/// each member of the collection, folding it into a single value.
+///
+/// The lambda takes two parameters: the accumulated value, starting from
+/// `initial`, and the current member. Its result is the accumulator for the
+/// next member, and the result of the last application is returned.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/collection/reduce).
+#[derive(Serialize, Clone, Debug)]
+pub struct Reduce<'a> {
+    reduce: Expr<'a>,
+    initial: Expr<'a>,
+    collection: Expr<'a>,
+}
+
+impl<'a> Reduce<'a> {
+    pub fn new(
+        lambda: Lambda<'a>,
+        initial: impl Into<Expr<'a>>,
+        collection: impl Into<Expr<'a>>,
+    ) -> Self {
+        Self {
+            reduce: Expr::from(lambda),
+            initial: initial.into(),
+            collection: collection.into(),
+        }
+    }
+}
+
+/// The `Reverse` function returns a collection of the same type with the
+/// elements in reversed order.
+///
+/// Works with arrays, pages and sets. A reversed page keeps its cursors, so
+/// paginating a reversed set continues in the reversed order.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/collection/reverse).
+#[derive(Serialize, Clone, Debug)]
+pub struct Reverse<'a> {
+    reverse: Expr<'a>,
+}
+
+impl<'a> Reverse<'a> {
+    pub fn new(collection: impl Into<Expr<'a>>) -> Self {
+        Self {
+            reverse: collection.into(),
+        }
+    }
+}
+
 /// The `Take` function returns a new collection of the same type that contains
 /// num elements from the head of the collection.
 ///
@@ -354,6 +405,42 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_reduce() {
+        let fun = Reduce::new(
+            Lambda::new(
+                Array::from(vec!["acc", "x"]),
+                Add::new(Array::from(vec![Var::new("acc"), Var::new("x")])),
+            ),
+            0,
+            Array::from(vec![1, 2, 3]),
+        );
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "reduce": {
+                "lambda": ["acc", "x"],
+                "expr": {"add": [{"var": "acc"}, {"var": "x"}]}
+            },
+            "initial": 0,
+            "collection": [1, 2, 3],
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let fun = Reverse::new(Array::from(vec![1, 2, 3]));
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(json!({"reverse": [1, 2, 3]}), serialized);
+    }
+
     #[test]
     fn test_is_empty() {
         let fun = IsEmpty::new(Array::from(vec![1, 2, 3]));
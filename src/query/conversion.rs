@@ -1,7 +1,28 @@
 //! Conversion functions
 use crate::{expr::Expr, query::Query};
 
-query![ToDate, ToNumber, ToString, ToTime];
+query![ToArray, ToDate, ToDouble, ToInteger, ToNumber, ToObject, ToString, ToTime];
+
+/// The `ToArray` function converts an object to an array of `[key, value]`
+/// pairs, e.g. for iterating the fields of an object in a `Lambda`.
+///
+/// Attempting to convert a value which is not an object results in an
+/// "invalid argument" error.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/conversion/toarray).
+#[derive(Serialize, Debug, Clone)]
+pub struct ToArray<'a> {
+    to_array: Expr<'a>,
+}
+
+impl<'a> ToArray<'a> {
+    pub fn new(expr: impl Into<Expr<'a>>) -> Self {
+        Self {
+            to_array: expr.into(),
+        }
+    }
+}
 
 /// The `ToDate` function converts a value to a date type, if possible.
 ///
@@ -23,6 +44,48 @@ impl<'a> ToDate<'a> {
     }
 }
 
+/// The `ToDouble` function converts a value to a double-precision floating
+/// point number, if possible.
+///
+/// Attempting to convert a value which has no numeric representation results
+/// in an "invalid argument" error.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/conversion/todouble).
+#[derive(Serialize, Debug, Clone)]
+pub struct ToDouble<'a> {
+    to_double: Expr<'a>,
+}
+
+impl<'a> ToDouble<'a> {
+    pub fn new(expr: impl Into<Expr<'a>>) -> Self {
+        Self {
+            to_double: expr.into(),
+        }
+    }
+}
+
+/// The `ToInteger` function converts a value to an integer, if possible. A
+/// double is truncated to an integer.
+///
+/// Attempting to convert a value which has no numeric representation results
+/// in an "invalid argument" error.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/conversion/tointeger).
+#[derive(Serialize, Debug, Clone)]
+pub struct ToInteger<'a> {
+    to_integer: Expr<'a>,
+}
+
+impl<'a> ToInteger<'a> {
+    pub fn new(expr: impl Into<Expr<'a>>) -> Self {
+        Self {
+            to_integer: expr.into(),
+        }
+    }
+}
+
 /// The `ToNumber` function converts a value to a numeric literal, if possible.
 ///
 /// Attempting to convert a value to a number which has no numeric
@@ -43,11 +106,42 @@ impl<'a> ToNumber<'a> {
     }
 }
 
+/// The `ToObject` function converts an array of `[key, value]` pairs to an
+/// object, reversing [ToArray](struct.ToArray.html).
+///
+/// Attempting to convert a value which is not such an array results in an
+/// "invalid argument" error.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/conversion/toobject).
+#[derive(Serialize, Debug, Clone)]
+pub struct ToObject<'a> {
+    to_object: Expr<'a>,
+}
+
+impl<'a> ToObject<'a> {
+    pub fn new(expr: impl Into<Expr<'a>>) -> Self {
+        Self {
+            to_object: expr.into(),
+        }
+    }
+}
+
 /// The `ToString` function converts a value to a string type, if possible.
 ///
 /// Attempting to convert a value to a string which has no string representation
 /// results in an "invalid argument" error.
 ///
+/// The string forms produced by Fauna are:
+///
+/// * A `@ts` timestamp is rendered in ISO 8601 in UTC, with nanoseconds only if
+///   set, e.g. `2015-02-20T06:30:00Z`.
+/// * A `@date` is rendered as `YYYY-MM-DD`, e.g. `2011-07-07`.
+/// * A `Ref` has no string representation, and converting it results in an
+///   "invalid argument" error. Use [Ref::path](../../expr/struct.Ref.html#method.path)
+///   or the `Display` implementation of the `Ref` to stringify refs on the
+///   client side.
+///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/conversion/tostring).
 #[derive(Serialize, Debug, Clone)]
@@ -85,9 +179,54 @@ impl<'a> ToTime<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::prelude::*;
+    use crate::{error::Error, prelude::*, test_utils::CLIENT};
+    use chrono::{offset::TimeZone, NaiveDate, Utc};
     use serde_json::{self, json};
 
+    #[test]
+    fn test_to_array() {
+        let mut data = Object::default();
+        data.insert("name", "Musti");
+
+        let query = Query::from(ToArray::new(data));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "to_array": {"object": {"name": "Musti"}},
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_to_object() {
+        let pairs = Array::from(vec![Array::from(vec!["name", "Musti"])]);
+
+        let query = Query::from(ToObject::new(pairs));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "to_object": [["name", "Musti"]],
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_to_object_to_array_round_trip() {
+        let mut data = Object::default();
+        data.insert("name", "Musti");
+
+        let query = Query::from(ToObject::new(ToArray::new(data)));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "to_object": {"to_array": {"object": {"name": "Musti"}}},
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_to_date() {
         let fun = ToDate::new("2019-06-06");
@@ -101,6 +240,32 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_to_double() {
+        let fun = ToDouble::new(2);
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "to_double": 2,
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_to_integer() {
+        let fun = ToInteger::new(2.5);
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "to_integer": 2.5,
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_to_number() {
         let fun = ToNumber::new("2");
@@ -139,4 +304,39 @@ mod tests {
 
         assert_eq!(expected, serialized);
     }
+
+    #[test]
+    fn test_to_string_timestamp_eval() {
+        let ts = Utc.timestamp_opt(1_424_413_800, 0).unwrap();
+        let response = CLIENT.query(ToString::new(ts)).unwrap();
+
+        assert_eq!(Some("2015-02-20T06:30:00Z"), response.resource.as_str());
+    }
+
+    #[test]
+    fn test_to_string_date_eval() {
+        let date = NaiveDate::from_ymd_opt(2011, 7, 7).unwrap();
+        let response = CLIENT.query(ToString::new(date)).unwrap();
+
+        assert_eq!(Some("2011-07-07"), response.resource.as_str());
+    }
+
+    #[test]
+    fn test_to_string_ref_eval() {
+        match CLIENT.query(ToString::new(Ref::class("x"))) {
+            Err(Error::BadRequest(errors)) => {
+                assert_eq!("invalid argument", errors.errors[0].code);
+            }
+            other => panic!("Expected an invalid argument error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ref_display() {
+        let mut reference = Ref::instance("musti");
+        reference.set_class("cats");
+
+        assert_eq!("Ref(id=musti,class=classes/cats)", reference.to_string());
+        assert_eq!("classes/cats/musti", reference.path());
+    }
 }
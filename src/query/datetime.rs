@@ -1,7 +1,7 @@
 //! Time and date functions
 use crate::{expr::Expr, query::Query};
 
-query![Date, Epoch, Time];
+query![Date, Epoch, Now, Time];
 
 /// The `Date` function constructs a Date from an ISO 8601 formatted string.
 ///
@@ -32,6 +32,13 @@ pub enum EpochUnit {
     Nanosecond,
 }
 
+string_enum!(EpochUnit, "Unknown epoch unit", {
+    Second => "second",
+    Millisecond => "millisecond",
+    Microsecond => "microsecond",
+    Nanosecond => "nanosecond",
+});
+
 /// The `Epoch` function constructs a Timestamp relative to the epoch
 /// (1970-01-01T00:00:00Z).
 ///
@@ -55,6 +62,25 @@ impl<'a> Epoch<'a> {
     }
 }
 
+/// The `Now` function returns the transaction time of the query as a
+/// Timestamp.
+///
+/// Every call to `Now` in the same transaction returns the same timestamp,
+/// which is the same as `Time::new("now")`.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/time_date/now)
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Now<'a> {
+    now: Expr<'a>,
+}
+
+impl<'a> Now<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
 /// The `Time` function constructs a Timestamp from an ISO 8601 string.
 ///
 /// The special string now may be used to construct a time from the current
@@ -80,9 +106,28 @@ impl<'a> Time<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::prelude::*;
+    use crate::{prelude::*, test_utils::*};
     use serde_json::{self, json};
 
+    #[test]
+    fn test_datetime_functions_in_query_eval() {
+        let same_instant = Equals::new(
+            Epoch::new(60, EpochUnit::Second),
+            Time::new("1970-01-01T00:01:00Z"),
+        );
+
+        let response = CLIENT.query(same_instant).unwrap();
+        assert_eq!(Some(true), response.resource.as_bool());
+
+        let response = CLIENT
+            .query(Select::new(
+                vec![0],
+                Array::from(vec![Date::new("1970-01-02")]),
+            ))
+            .unwrap();
+        assert!(response.resource.is_date());
+    }
+
     #[test]
     fn test_date() {
         let fun = Date::new("1970-01-01");
@@ -112,6 +157,31 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_epoch_unit_strings() {
+        for unit in &[
+            EpochUnit::Second,
+            EpochUnit::Millisecond,
+            EpochUnit::Microsecond,
+            EpochUnit::Nanosecond,
+        ] {
+            let name = unit.to_string();
+
+            assert_eq!(json!(name), serde_json::to_value(unit).unwrap());
+            assert_eq!(json!(unit), json!(name.parse::<EpochUnit>().unwrap()));
+        }
+
+        assert!("fortnight".parse::<EpochUnit>().is_err());
+    }
+
+    #[test]
+    fn test_now() {
+        let query = Query::from(Now::new());
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(json!({"now": null}), serialized);
+    }
+
     #[test]
     fn test_time() {
         let fun = Time::new("1970-01-01T00:00:00+00:00");
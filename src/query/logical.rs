@@ -1,11 +1,27 @@
 //! Logical functions
 use crate::{
-    expr::{Expr, Ref},
-    query::Query,
+    expr::{Array, Expr, Ref},
+    query::{set::Match, Query},
 };
 use chrono::{DateTime, Utc};
 
-query![And, Or, Not, Contains, Exists, Equals, Lt, Lte, Gt, Gte];
+query![
+    And,
+    Or,
+    Not,
+    Contains,
+    ContainsField,
+    ContainsPath,
+    ContainsValue,
+    Exists,
+    Equals,
+    Lt,
+    Lte,
+    Gt,
+    Gte
+];
+
+non_empty![iter: And, Or, Equals];
 
 /// The `And` function computes the conjunction of a list of boolean values,
 /// returning `true` if all elements are "true", and `false` otherwise.
@@ -129,7 +145,7 @@ pub struct Lt<'a> {
 }
 
 impl<'a> Lt<'a> {
-    /// A simple and with two expressions. For a vector comparison, use the
+    /// A comparison of two expressions. For comparing more values, use the
     /// `From` trait.
     pub fn new(left: impl Into<Expr<'a>>, right: impl Into<Expr<'a>>) -> Self {
         Self {
@@ -138,6 +154,8 @@ impl<'a> Lt<'a> {
     }
 }
 
+/// The values are compared in the order of the iterator, so e.g. `Lt::from(vec![a, b, c])`
+/// checks the pair `a` and `b`, and then `b` and `c`.
 impl<'a, I, E> From<I> for Lt<'a>
 where
     I: IntoIterator<Item = E>,
@@ -161,7 +179,7 @@ pub struct Lte<'a> {
 }
 
 impl<'a> Lte<'a> {
-    /// A simple and with two expressions. For a vector comparison, use the
+    /// A comparison of two expressions. For comparing more values, use the
     /// `From` trait.
     pub fn new(left: impl Into<Expr<'a>>, right: impl Into<Expr<'a>>) -> Self {
         Self {
@@ -170,6 +188,8 @@ impl<'a> Lte<'a> {
     }
 }
 
+/// The values are compared in the order of the iterator, so e.g. `Lte::from(vec![a, b, c])`
+/// checks the pair `a` and `b`, and then `b` and `c`.
 impl<'a, I, E> From<I> for Lte<'a>
 where
     I: IntoIterator<Item = E>,
@@ -193,7 +213,7 @@ pub struct Gt<'a> {
 }
 
 impl<'a> Gt<'a> {
-    /// A simple and with two expressions. For a vector comparison, use the
+    /// A comparison of two expressions. For comparing more values, use the
     /// `From` trait.
     pub fn new(left: impl Into<Expr<'a>>, right: impl Into<Expr<'a>>) -> Self {
         Self {
@@ -202,6 +222,8 @@ impl<'a> Gt<'a> {
     }
 }
 
+/// The values are compared in the order of the iterator, so e.g. `Gt::from(vec![a, b, c])`
+/// checks the pair `a` and `b`, and then `b` and `c`.
 impl<'a, I, E> From<I> for Gt<'a>
 where
     I: IntoIterator<Item = E>,
@@ -225,7 +247,7 @@ pub struct Gte<'a> {
 }
 
 impl<'a> Gte<'a> {
-    /// A simple and with two expressions. For a vector comparison, use the
+    /// A comparison of two expressions. For comparing more values, use the
     /// `From` trait.
     pub fn new(left: impl Into<Expr<'a>>, right: impl Into<Expr<'a>>) -> Self {
         Self {
@@ -234,6 +256,8 @@ impl<'a> Gte<'a> {
     }
 }
 
+/// The values are compared in the order of the iterator, so e.g. `Gte::from(vec![a, b, c])`
+/// checks the pair `a` and `b`, and then `b` and `c`.
 impl<'a, I, E> From<I> for Gte<'a>
 where
     I: IntoIterator<Item = E>,
@@ -272,6 +296,69 @@ impl<'a> Contains<'a> {
     }
 }
 
+/// The `ContainsField` function returns `true` if the object or the document
+/// in `in_` has a top-level field with the given name.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/containsfield)
+#[derive(Serialize, Debug, Clone)]
+pub struct ContainsField<'a> {
+    contains_field: Expr<'a>,
+    #[serde(rename = "in")]
+    in_: Expr<'a>,
+}
+
+impl<'a> ContainsField<'a> {
+    pub fn new(field: impl Into<Expr<'a>>, in_: impl Into<Expr<'a>>) -> Self {
+        Self {
+            contains_field: field.into(),
+            in_: in_.into(),
+        }
+    }
+}
+
+/// The `ContainsPath` function returns `true` if a value exists in the given
+/// path of `in_`. The path is an array of field names and array indices.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/containspath)
+#[derive(Serialize, Debug, Clone)]
+pub struct ContainsPath<'a> {
+    contains_path: Array<'a>,
+    #[serde(rename = "in")]
+    in_: Expr<'a>,
+}
+
+impl<'a> ContainsPath<'a> {
+    pub fn new(path: impl Into<Array<'a>>, in_: impl Into<Expr<'a>>) -> Self {
+        Self {
+            contains_path: path.into(),
+            in_: in_.into(),
+        }
+    }
+}
+
+/// The `ContainsValue` function returns `true` if the value is found in the
+/// top-level values of the array, object, document or set in `in_`.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/containsvalue)
+#[derive(Serialize, Debug, Clone)]
+pub struct ContainsValue<'a> {
+    contains_value: Expr<'a>,
+    #[serde(rename = "in")]
+    in_: Expr<'a>,
+}
+
+impl<'a> ContainsValue<'a> {
+    pub fn new(value: impl Into<Expr<'a>>, in_: impl Into<Expr<'a>>) -> Self {
+        Self {
+            contains_value: value.into(),
+            in_: in_.into(),
+        }
+    }
+}
+
 /// The `Exists` function returns boolean `true` if the provided ref exists at the
 /// specified timestamp (in the case of an instance), or is non-empty (in the
 /// case of a set), and `false` otherwise.
@@ -293,6 +380,14 @@ impl<'a> Exists<'a> {
         }
     }
 
+    /// Checks if any instance matches the `terms` in the `index`.
+    pub fn by_match(index: impl Into<Expr<'a>>, terms: impl Into<Expr<'a>>) -> Self {
+        Self {
+            exists: Expr::from(Match::new(index).with_terms(terms)),
+            timestamp: None,
+        }
+    }
+
     pub fn timestamp(&mut self, ts: DateTime<Utc>) -> &mut Self {
         self.timestamp = Some(Expr::from(ts));
         self
@@ -301,10 +396,26 @@ impl<'a> Exists<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::prelude::*;
+    use crate::{prelude::*, test_utils::CLIENT};
     use chrono::{offset::TimeZone, Utc};
     use serde_json::{self, json};
 
+    fn evaluates_to(expected: bool, query: impl Into<Expr<'static>>) {
+        let response = CLIENT.query(query).unwrap();
+        assert_eq!(Some(expected), response.resource.as_bool());
+    }
+
+    #[test]
+    fn test_try_new() {
+        let serialized =
+            serde_json::to_value(Query::from(And::try_new(vec![true]).unwrap())).unwrap();
+        assert_eq!(json!({"and": [true]}), serialized);
+
+        assert!(And::try_new(Vec::<bool>::new()).is_err());
+        assert!(Or::try_new(Vec::<bool>::new()).is_err());
+        assert!(Equals::try_new(Vec::<bool>::new()).is_err());
+    }
+
     #[test]
     fn test_and() {
         let aaaand = And::from(vec![true, true, false]);
@@ -341,10 +452,46 @@ mod tests {
         assert_eq!(json!({"equals": ["musti", "naukio"]}), serialized);
     }
 
+    #[test]
+    fn test_contains_field() {
+        let fun = ContainsField::new("name", Var::new("cat"));
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(
+            json!({"contains_field": "name", "in": {"var": "cat"}}),
+            serialized
+        );
+    }
+
+    #[test]
+    fn test_contains_path() {
+        let path = Array::from(vec![Expr::from("friends"), Expr::from(0)]);
+        let query = Query::from(ContainsPath::new(path, Var::new("cat")));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(
+            json!({"contains_path": ["friends", 0], "in": {"var": "cat"}}),
+            serialized
+        );
+    }
+
+    #[test]
+    fn test_contains_value() {
+        let fun = ContainsValue::new("Musti", Array::from(vec!["Musti", "Naukio"]));
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(
+            json!({"contains_value": "Musti", "in": ["Musti", "Naukio"]}),
+            serialized
+        );
+    }
+
     #[test]
     fn test_exists() {
         let mut exists = Exists::new(Ref::instance("Musti"));
-        exists.timestamp(Utc.timestamp(60, 0));
+        exists.timestamp(Utc.timestamp_opt(60, 0).unwrap());
 
         let query = Query::from(exists);
         let serialized = serde_json::to_value(&query).unwrap();
@@ -356,13 +503,26 @@ mod tests {
                 }
             },
             "ts": {
-                "@ts": Utc.timestamp(60, 0)
+                "@ts": Utc.timestamp_opt(60, 0).unwrap()
             }
         });
 
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_exists_by_match() {
+        let exists = Exists::by_match(Index::find("cats_by_name"), "Musti");
+        let query = Query::from(exists);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "exists": {"match": {"index": "cats_by_name"}, "terms": "Musti"}
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_lt() {
         let lt = Lt::new(1, 2);
@@ -398,4 +558,39 @@ mod tests {
 
         assert_eq!(json!({"gte": [1, 2, 3]}), serialized);
     }
+
+    #[test]
+    fn test_variadic_comparison_keeps_order() {
+        let lt = Lt::from(vec![3, 1, 2]);
+        let query = Query::from(lt);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(json!({"lt": [3, 1, 2]}), serialized);
+    }
+
+    #[test]
+    fn test_lt_eval() {
+        evaluates_to(true, Lt::from(vec![1, 2, 3]));
+        evaluates_to(false, Lt::from(vec![1, 3, 2]));
+        evaluates_to(false, Lt::from(vec![1, 1, 2]));
+    }
+
+    #[test]
+    fn test_lte_eval() {
+        evaluates_to(true, Lte::from(vec![1, 1, 2]));
+        evaluates_to(false, Lte::from(vec![1, 3, 2]));
+    }
+
+    #[test]
+    fn test_gt_eval() {
+        evaluates_to(true, Gt::from(vec![3, 2, 1]));
+        evaluates_to(false, Gt::from(vec![3, 1, 2]));
+        evaluates_to(false, Gt::from(vec![3, 3, 1]));
+    }
+
+    #[test]
+    fn test_gte_eval() {
+        evaluates_to(true, Gte::from(vec![3, 3, 1]));
+        evaluates_to(false, Gte::from(vec![3, 1, 2]));
+    }
 }
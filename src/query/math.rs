@@ -8,6 +8,8 @@ query![
     Sqrt, Subtract, Tan, Tanh, Trunc
 ];
 
+non_empty![array: Add, BitAnd, BitOr, BitXor, Divide, Max, Min, Modulo, Multiply, Subtract];
+
 /// The `Abs` function is used to get the absolute value of a number.
 ///
 /// Read the
@@ -387,7 +389,7 @@ impl<'a> Min<'a> {
 ///
 /// Attempting to call modulo with an empty array results in the error
 /// "Non-empty array expected." Check to make sure at least one argument is
-/// passed to modulo function.
+/// passed to modulo function, e.g. with [try_new](#method.try_new).
 ///
 /// Attempting to compute the remainder of a division by zero results in the
 /// error "Illegal division by zero."
@@ -412,7 +414,7 @@ impl<'a> Modulo<'a> {
 ///
 /// Attempting to call multiply without any arguments results in the error
 /// "Non-empty array expected." Check to make sure at least one argument is
-/// passed to the multiply function.
+/// passed to the multiply function, e.g. with [try_new](#method.try_new).
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/multiply)
@@ -649,9 +651,27 @@ impl<'a> Trunc<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::prelude::*;
+    use crate::{error::Error, prelude::*};
     use serde_json::{self, json};
 
+    #[test]
+    fn test_try_new() {
+        let fun = Multiply::try_new(vec![2, 3]).unwrap();
+        let serialized = serde_json::to_value(Query::from(fun)).unwrap();
+
+        assert_eq!(json!({"multiply": [2, 3]}), serialized);
+
+        match Multiply::try_new(Vec::<i64>::new()) {
+            Err(Error::RequestDataFailure(msg)) => {
+                assert_eq!("Multiply requires at least one argument", msg)
+            }
+            res => panic!("Expected an error, got {:?}", res),
+        }
+
+        assert!(Add::try_new(Vec::<i64>::new()).is_err());
+        assert!(Modulo::try_new(Vec::<i64>::new()).is_err());
+    }
+
     #[test]
     fn test_abs() {
         let abs = Abs::new(-1);
@@ -208,9 +208,44 @@ impl<'a> NewId<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::prelude::*;
+    use crate::{error::Error, prelude::*, test_utils::*};
     use serde_json::{self, json};
 
+    fn abort_over_two() -> Lambda<'static> {
+        Lambda::new(
+            "x",
+            If::cond(
+                Gt::new(Var::new("x"), 2),
+                Abort::new("too big"),
+                Var::new("x"),
+            ),
+        )
+    }
+
+    fn assert_aborted(result: crate::Result<Response>) {
+        match result {
+            Err(error @ Error::BadRequest(_)) => {
+                assert_eq!(Some("too big"), error.abort_message())
+            }
+            res => panic!("Expected an aborted transaction, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_abort_in_map_eval() {
+        let map = Map::new(Array::from(vec![1, 2, 3]), abort_over_two());
+        assert_aborted(CLIENT.query(map));
+
+        let map = Map::new(Array::from(vec![1, 2]), abort_over_two());
+        assert!(CLIENT.query(map).is_ok());
+    }
+
+    #[test]
+    fn test_abort_in_foreach_eval() {
+        let foreach = Foreach::new(Array::from(vec![3, 1]), abort_over_two());
+        assert_aborted(CLIENT.query(foreach));
+    }
+
     #[test]
     fn test_abort() {
         let fun = Abort::new("BOOM");
@@ -442,4 +477,37 @@ mod tests {
 
         assert_eq!(expected, serialized);
     }
+
+    #[test]
+    fn test_indexes_and_new_id_eval() {
+        with_scoped_client(|client, _| {
+            let class_name = gen_db_name();
+            let index_name = gen_db_name();
+
+            client
+                .query(CreateClass::new(ClassParams::new(&class_name)))
+                .unwrap();
+
+            client
+                .query(CreateIndex::new(IndexParams::new(
+                    &index_name,
+                    Class::find(class_name.as_str()),
+                )))
+                .unwrap();
+
+            let response = client.query(Paginate::new(Indexes::all())).unwrap();
+            assert_eq!(1, response.resource["data"].as_array().unwrap().len());
+
+            let response = client
+                .query(Get::instance(Index::find(index_name.as_str())))
+                .unwrap();
+            assert_eq!(
+                Some(index_name.as_str()),
+                response.resource["name"].as_str()
+            );
+
+            let response = client.query(NewId::new()).unwrap();
+            assert!(response.resource.as_str().is_some());
+        })
+    }
 }
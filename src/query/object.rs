@@ -0,0 +1,95 @@
+//! Object functions
+use crate::{
+    expr::Expr,
+    query::{basic::Lambda, Query},
+};
+
+query![Merge];
+
+/// The `Merge` function combines two objects into one, doing a shallow merge.
+///
+/// The keys of the `with` object override the keys of the `merge` object. A
+/// key with a `null` value in `with` is removed from the result. Conflicting
+/// keys can be resolved with a [resolver](#method.resolver) lambda taking the
+/// key, the left value and the right value.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/object/merge).
+#[derive(Serialize, Debug, Clone)]
+pub struct Merge<'a> {
+    merge: Expr<'a>,
+    with: Expr<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lambda: Option<Expr<'a>>,
+}
+
+impl<'a> Merge<'a> {
+    /// Both `merge` and `with` must evaluate to objects.
+    pub fn new(merge: impl Into<Expr<'a>>, with: impl Into<Expr<'a>>) -> Self {
+        Self {
+            merge: merge.into(),
+            with: with.into(),
+            lambda: None,
+        }
+    }
+
+    /// The lambda to pick the value of a key found in both objects.
+    pub fn resolver(&mut self, lambda: Lambda<'a>) -> &mut Self {
+        self.lambda = Some(Expr::from(lambda));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use serde_json::{self, json};
+
+    #[test]
+    fn test_merge() {
+        let mut left = Object::default();
+        left.insert("name", "Musti");
+
+        let mut right = Object::default();
+        right.insert("age", 7);
+
+        let query = Query::from(Merge::new(left, right));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "merge": {"object": {"name": "Musti"}},
+            "with": {"object": {"age": 7}},
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_merge_with_resolver() {
+        let mut left = Object::default();
+        left.insert("age", 7);
+
+        let mut right = Object::default();
+        right.insert("age", 8);
+
+        let mut merge = Merge::new(left, right);
+        merge.resolver(Lambda::new(
+            Array::from(vec!["key", "left", "right"]),
+            Var::new("left"),
+        ));
+
+        let query = Query::from(merge);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "merge": {"object": {"age": 7}},
+            "with": {"object": {"age": 8}},
+            "lambda": {
+                "lambda": ["key", "left", "right"],
+                "expr": {"var": "left"},
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+}
@@ -1,7 +1,13 @@
 //! Read functions
 use crate::{
-    expr::{Array, Expr},
-    query::Query,
+    error::Error,
+    expr::{Array, Expr, Object},
+    query::{
+        basic::{Binding, If, Let, Var, PROJECTION_VAR, SELECTED_VAR},
+        logical::Equals,
+        set::Match,
+        Query,
+    },
 };
 use chrono::{DateTime, Utc};
 
@@ -30,6 +36,11 @@ impl<'a> Get<'a> {
         }
     }
 
+    /// Retrieves the first instance matching the `terms` in the `index`.
+    pub fn by_match(index: impl Into<Expr<'a>>, terms: impl Into<Expr<'a>>) -> Self {
+        Self::instance(Match::new(index).with_terms(terms))
+    }
+
     pub fn timestamp(&mut self, ts: DateTime<Utc>) -> &mut Self {
         self.timestamp = Some(Expr::from(ts));
         self
@@ -177,6 +188,18 @@ impl<'a> Select<'a> {
         self.default = Some(default.into());
         self
     }
+
+    /// Returns the `default` both if the path does not exist and if the value
+    /// in the path is `null`. The selected value is bound in a `Let`, so the
+    /// path is read only once.
+    pub fn default_if_null(mut self, default: impl Into<Expr<'a>>) -> Let<'a> {
+        self.default = Some(Expr::null());
+
+        let is_null = Equals::new(Var::new(SELECTED_VAR), Expr::null());
+        let choice = If::cond(is_null, default, Var::new(SELECTED_VAR));
+
+        Let::bindings(vec![Binding::new(SELECTED_VAR, self)], choice)
+    }
 }
 
 /// The `SelectAll` function extracts one or more values from a document.
@@ -203,16 +226,91 @@ impl<'a> SelectAll<'a> {
     }
 }
 
+/// Reads multiple fields of an instance with a single `Get`, returning them
+/// as an object.
+///
+/// Builds a `Let` with the instance bound to a variable and a `Select` for
+/// every field, so the instance is read only once.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// let mut projection = Projection::new(Ref::instance("musti"));
+/// projection.field("name", vec!["data", "name"]);
+/// projection.field("age", vec!["data", "age"]);
+///
+/// let query = Expr::from(projection);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Projection<'a> {
+    reference: Expr<'a>,
+    fields: Vec<(&'a str, Array<'a>)>,
+}
+
+impl<'a> Projection<'a> {
+    pub fn new(reference: impl Into<Expr<'a>>) -> Self {
+        Self {
+            reference: reference.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Selects the value in the `path` as the `name` field of the result.
+    pub fn field(&mut self, name: &'a str, path: impl Into<Array<'a>>) -> &mut Self {
+        debug_assert!(
+            !self.has_field(name),
+            "The same field is selected more than once in a Projection"
+        );
+
+        self.fields.push((name, path.into()));
+        self
+    }
+
+    /// Selects the value in the `path` as the `name` field of the result,
+    /// returning an error if the `name` field is already selected.
+    pub fn try_field(
+        &mut self,
+        name: &'a str,
+        path: impl Into<Array<'a>>,
+    ) -> crate::Result<&mut Self> {
+        if self.has_field(name) {
+            return Err(Error::RequestDataFailure(
+                "The same field is selected more than once in a Projection",
+            ));
+        }
+
+        self.fields.push((name, path.into()));
+        Ok(self)
+    }
+
+    fn has_field(&self, name: &str) -> bool {
+        self.fields.iter().any(|(field, _)| *field == name)
+    }
+}
+
+impl<'a> From<Projection<'a>> for Query<'a> {
+    fn from(projection: Projection<'a>) -> Self {
+        let mut fields = Object::default();
+
+        for (name, path) in projection.fields {
+            fields.insert(name, Select::new(path, Var::new(PROJECTION_VAR)));
+        }
+
+        let doc = Binding::new(PROJECTION_VAR, Get::instance(projection.reference));
+
+        Query::from(Let::bindings(vec![doc], fields))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::prelude::*;
+    use crate::{error::Error, prelude::*};
     use chrono::{offset::TimeZone, Utc};
     use serde_json::{self, json};
 
     #[test]
     fn test_get() {
         let mut get = Get::instance(Ref::instance("musti"));
-        get.timestamp(Utc.timestamp(60, 0));
+        get.timestamp(Utc.timestamp_opt(60, 0).unwrap());
 
         let query = Query::from(get);
         let serialized = serde_json::to_value(&query).unwrap();
@@ -224,13 +322,58 @@ mod tests {
                 }
             },
             "ts": {
-                "@ts": Utc.timestamp(60, 0)
+                "@ts": Utc.timestamp_opt(60, 0).unwrap()
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_get_by_match() {
+        let get = Get::by_match(Index::find("cats_by_name"), "Musti");
+        let query = Query::from(get);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "get": {"match": {"index": "cats_by_name"}, "terms": "Musti"}
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_projection() {
+        let mut projection = Projection::new(Ref::instance("musti"));
+        projection.field("name", vec!["data", "name"]);
+        projection.field("age", vec!["data", "age"]);
+
+        let serialized = serde_json::to_value(Query::from(projection)).unwrap();
+
+        let expected = json!({
+            "let": {"_v_doc": {"get": {"@ref": {"id": "musti"}}}},
+            "in": {
+                "object": {
+                    "name": {"select": ["data", "name"], "from": {"var": "_v_doc"}},
+                    "age": {"select": ["data", "age"], "from": {"var": "_v_doc"}}
+                }
             }
         });
 
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_projection_duplicate_fields() {
+        let mut projection = Projection::new(Ref::instance("musti"));
+        projection.try_field("name", vec!["data", "name"]).unwrap();
+
+        match projection.try_field("name", vec!["data", "nickname"]) {
+            Err(Error::RequestDataFailure(_)) => (),
+            _ => panic!("Expected an error for the duplicate field"),
+        }
+    }
+
     #[test]
     fn test_key_from_secret() {
         let fun = KeyFromSecret::new("Hunter2");
@@ -247,8 +390,8 @@ mod tests {
     #[test]
     fn test_paginate() {
         let mut fun = Paginate::new(Classes::all());
-        fun.before(Utc.timestamp(100, 0));
-        fun.after(Utc.timestamp(60, 0));
+        fun.before(Utc.timestamp_opt(100, 0).unwrap());
+        fun.after(Utc.timestamp_opt(60, 0).unwrap());
 
         let query = Query::from(fun);
         let serialized = serde_json::to_value(&query).unwrap();
@@ -265,6 +408,24 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_paginate_match() {
+        let mut fun = Paginate::new(Match::new(Index::find("cats_by_name")).with_terms("Musti"));
+        fun.size(10);
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "paginate": {"match": {"index": "cats_by_name"}, "terms": "Musti"},
+            "size": 10,
+            "sources": false,
+            "events": false,
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_select() {
         let mut path = Array::from(vec!["favorites", "foods"]);
@@ -291,6 +452,35 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_select_default_if_null() {
+        let fun = Select::new(
+            vec!["data", "nickname"],
+            Get::instance(Ref::instance("musti")),
+        )
+        .default_if_null("Musti");
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "let": {
+                "_v_selected": {
+                    "select": ["data", "nickname"],
+                    "from": {"get": {"@ref": {"id": "musti"}}},
+                    "default": null
+                }
+            },
+            "in": {
+                "if": {"equals": [{"var": "_v_selected"}, null]},
+                "then": "Musti",
+                "else": {"var": "_v_selected"}
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_select_all() {
         let mut path = Array::from(vec!["favorites", "foods"]);
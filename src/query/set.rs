@@ -4,7 +4,15 @@ use crate::{
     query::Query,
 };
 
-query![Difference, Distinct, Intersection, Join, Match, Union];
+query![
+    Difference,
+    Distinct,
+    Intersection,
+    Join,
+    Match,
+    Range,
+    Union
+];
 
 /// The `Difference` function returns a `SetRef` object that represents all elements
 /// in the first `SetRef` which are not in the difference `SetRef`(s).
@@ -129,6 +137,13 @@ impl<'a> Join<'a> {
 /// Collection of type Page. If no matching element is found an empty collection
 /// is returned.
 ///
+/// The constructors by the number of terms in the index:
+///
+/// - No terms: `Match::all(index)`, matching every instance of the index.
+/// - One term: `Match::new(index).with_terms(value)`.
+/// - Many terms: `Match::terms(index, vec![a, b])`, with a value for every
+///   term, in the order of the terms of the index.
+///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/set/match)
 #[derive(Serialize, Debug, Clone)]
@@ -147,12 +162,58 @@ impl<'a> Match<'a> {
         }
     }
 
+    /// Match an index with no terms. The `terms` field is left out of the
+    /// query, as Fauna expects for such indexes.
+    pub fn all(match_: impl Into<Expr<'a>>) -> Self {
+        Self::new(match_)
+    }
+
+    /// Match an index with many terms, with the values in the order of the
+    /// terms of the index.
+    pub fn terms<E>(match_: impl Into<Expr<'a>>, terms: Vec<E>) -> Self
+    where
+        E: Into<Expr<'a>>,
+    {
+        let terms: Vec<Expr<'a>> = terms.into_iter().map(Into::into).collect();
+        Self::new(match_).with_terms(Array::from(terms))
+    }
+
     pub fn with_terms(mut self, terms: impl Into<Expr<'a>>) -> Self {
         self.terms = Some(terms.into());
         self
     }
 }
 
+/// The `Range` function returns the elements of a `SetRef` with values between
+/// `from` and `to`, inclusive.
+///
+/// The values are compared in the order of the index values. An array of
+/// values matches the index values from the left, and an empty array leaves
+/// the range open from that side.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/set/range)
+#[derive(Serialize, Debug, Clone)]
+pub struct Range<'a> {
+    range: Expr<'a>,
+    from: Expr<'a>,
+    to: Expr<'a>,
+}
+
+impl<'a> Range<'a> {
+    pub fn new(
+        set: impl Into<Expr<'a>>,
+        from: impl Into<Expr<'a>>,
+        to: impl Into<Expr<'a>>,
+    ) -> Self {
+        Self {
+            range: set.into(),
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+}
+
 /// The `Union` function combines the results one or more `SetRef` objects.
 ///
 /// Read the
@@ -189,9 +250,56 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::prelude::*;
+    use crate::{prelude::*, test_utils::*};
     use serde_json::{self, json};
 
+    /// Creates instances numbered 1 to 4 with the tags `a` for 1, 2 and 4 and
+    /// `b` for 2, 3 and 4, indexed by the tags.
+    fn with_tagged_numbers<F>(f: F)
+    where
+        F: FnOnce(&SyncClient, &str) + std::panic::UnwindSafe,
+    {
+        with_class(|client, class_name| {
+            let index_name = gen_db_name();
+
+            let mut params = IndexParams::new(&index_name, Ref::class(class_name));
+            params.terms(vec![Term::field(vec!["data", "tags"])]);
+            params.values(vec![IndexValue::field(vec!["data", "n"])]);
+
+            client.query(CreateIndex::new(params)).unwrap();
+
+            let tagged = vec![
+                (1, vec!["a"]),
+                (2, vec!["a", "b"]),
+                (3, vec!["b"]),
+                (4, vec!["a", "b"]),
+            ];
+
+            for (n, tags) in tagged {
+                let mut data = Object::default();
+                data.insert("n", n);
+                data.insert("tags", Array::from(tags));
+
+                client
+                    .query(Create::new(Ref::class(class_name), data))
+                    .unwrap();
+            }
+
+            f(client, &index_name)
+        })
+    }
+
+    fn paginate_numbers<'a>(client: &SyncClient, set: impl Into<Expr<'a>>) -> Vec<u64> {
+        let response = client.query(Paginate::new(set)).unwrap();
+
+        response.resource["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n.as_u64().unwrap())
+            .collect()
+    }
+
     #[test]
     fn test_difference() {
         let fun = Difference::new(
@@ -212,6 +320,18 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_difference_eval() {
+        with_tagged_numbers(|client, index_name| {
+            let set = Difference::new(
+                Match::new(Ref::index(index_name)).with_terms("a"),
+                Match::new(Ref::index(index_name)).with_terms("b"),
+            );
+
+            assert_eq!(vec![1], paginate_numbers(client, set));
+        })
+    }
+
     #[test]
     fn test_distinct() {
         let fun = Distinct::new(Match::new(Index::find("spells_by_element")));
@@ -246,6 +366,18 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_intersection_eval() {
+        with_tagged_numbers(|client, index_name| {
+            let set = Intersection::new(
+                Match::new(Ref::index(index_name)).with_terms("a"),
+                Match::new(Ref::index(index_name)).with_terms("b"),
+            );
+
+            assert_eq!(vec![2, 4], paginate_numbers(client, set));
+        })
+    }
+
     #[test]
     fn test_join() {
         let mut owner = Ref::instance("wizard");
@@ -298,6 +430,76 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_match_without_terms() {
+        let query = Query::from(Match::all(Index::find("all_spells")));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(json!({"match": {"index": "all_spells"}}), serialized);
+        assert!(serialized.get("terms").is_none());
+    }
+
+    #[test]
+    fn test_match_many_terms() {
+        let fun = Match::terms(
+            Index::find("spells_by_element_and_cost"),
+            vec![Expr::from("fire"), Expr::from(3)],
+        );
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "match": {"index": "spells_by_element_and_cost"},
+            "terms": ["fire", 3]
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_range() {
+        let fun = Range::new(
+            Match::new(Index::find("spells_by_cost")),
+            3,
+            Array::from(vec![6]),
+        );
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "range": {
+                "match": {"index": "spells_by_cost"},
+            },
+            "from": 3,
+            "to": [6],
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_nested_set_functions() {
+        let union = Union::new(
+            Match::new(Index::find("spells_by_element")).with_terms("fire"),
+            Match::new(Index::find("spells_by_element")).with_terms("water"),
+        );
+
+        let serialized = serde_json::to_value(Query::from(Distinct::new(union))).unwrap();
+
+        let expected = json!({
+            "distinct": {
+                "union": [
+                    {"match": {"index": "spells_by_element"}, "terms": "fire"},
+                    {"match": {"index": "spells_by_element"}, "terms": "water"},
+                ]
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_union() {
         let fun = Union::new(
@@ -317,4 +519,16 @@ mod tests {
 
         assert_eq!(expected, serialized);
     }
+
+    #[test]
+    fn test_union_eval() {
+        with_tagged_numbers(|client, index_name| {
+            let set = Union::new(
+                Match::new(Ref::index(index_name)).with_terms("a"),
+                Match::new(Ref::index(index_name)).with_terms("b"),
+            );
+
+            assert_eq!(vec![1, 2, 3, 4], paginate_numbers(client, set));
+        })
+    }
 }
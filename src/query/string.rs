@@ -4,16 +4,21 @@ use crate::{expr::Expr, query::Query};
 query![
     CaseFold,
     Concat,
+    EndsWith,
     FindStr,
     FindStrRegex,
+    Format,
     LTrim,
     Length,
     LowerCase,
+    NGram,
     RTrim,
+    RegexEscape,
     Repeat,
     ReplaceStr,
     ReplaceStrRegex,
     Space,
+    StartsWith,
     SubString,
     TitleCase,
     Trim,
@@ -45,6 +50,14 @@ pub enum Normalizer {
     NFKD,
 }
 
+string_enum!(Normalizer, "Unknown normalizer", {
+    NFKCCaseFold => "NFKCCaseFold",
+    NFC => "NFC",
+    NFD => "NFD",
+    NFKC => "NFKC",
+    NFKD => "NFKD",
+});
+
 impl<'a> CaseFold<'a> {
     pub fn new(string: impl Into<Expr<'a>>) -> Self {
         Self {
@@ -79,6 +92,26 @@ impl<'a> Concat<'a> {
     }
 }
 
+/// The `EndsWith` function returns `true` if the string ends with the
+/// `search` string.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/endswith)
+#[derive(Serialize, Clone, Debug)]
+pub struct EndsWith<'a> {
+    endswith: Expr<'a>,
+    search: Expr<'a>,
+}
+
+impl<'a> EndsWith<'a> {
+    pub fn new(value: impl Into<Expr<'a>>, search: impl Into<Expr<'a>>) -> Self {
+        Self {
+            endswith: value.into(),
+            search: search.into(),
+        }
+    }
+}
+
 /// The `FindStr` function returns the offset position of a string in another
 /// string, or `-1` if the string is not found.
 ///
@@ -140,6 +173,28 @@ impl<'a> FindStrRegex<'a> {
     }
 }
 
+/// The `Format` function formats the `values` into a string with a
+/// printf-style format string, such as `"%d cats"`.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/format)
+#[derive(Serialize, Clone, Debug)]
+pub struct Format<'a> {
+    format: Expr<'a>,
+    values: Expr<'a>,
+}
+
+impl<'a> Format<'a> {
+    /// The `values` must evaluate to an array with a value for every
+    /// conversion in the `format`.
+    pub fn new(format: impl Into<Expr<'a>>, values: impl Into<Expr<'a>>) -> Self {
+        Self {
+            format: format.into(),
+            values: values.into(),
+        }
+    }
+}
+
 /// The `LTrim` function removes all white spaces, tabs, and new lines from the
 /// beginning of a string.
 ///
@@ -211,6 +266,65 @@ impl<'a> LowerCase<'a> {
     }
 }
 
+/// The `NGram` function returns an array of the n-grams of a string, such as
+/// for building the terms of an autocomplete index.
+///
+/// The n-grams are from 1 to 2 characters long, unless set otherwise with
+/// [min](#method.min) and [max](#method.max).
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/ngram)
+#[derive(Serialize, Clone, Debug)]
+pub struct NGram<'a> {
+    ngram: Expr<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min: Option<Expr<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max: Option<Expr<'a>>,
+}
+
+impl<'a> NGram<'a> {
+    pub fn new(string: impl Into<Expr<'a>>) -> Self {
+        Self {
+            ngram: string.into(),
+            min: None,
+            max: None,
+        }
+    }
+
+    /// The length of the shortest n-gram.
+    pub fn min(&mut self, min: impl Into<Expr<'a>>) -> &mut Self {
+        self.min = Some(min.into());
+        self
+    }
+
+    /// The length of the longest n-gram.
+    pub fn max(&mut self, max: impl Into<Expr<'a>>) -> &mut Self {
+        self.max = Some(max.into());
+        self
+    }
+}
+
+/// The `RegexEscape` function escapes the regular expression syntax in a
+/// string, so it can be used as a literal part of the pattern in
+/// [FindStrRegex](struct.FindStrRegex.html) or
+/// [ReplaceStrRegex](struct.ReplaceStrRegex.html).
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/regexescape)
+#[derive(Serialize, Clone, Debug)]
+pub struct RegexEscape<'a> {
+    regexescape: Expr<'a>,
+}
+
+impl<'a> RegexEscape<'a> {
+    pub fn new(string: impl Into<Expr<'a>>) -> Self {
+        Self {
+            regexescape: string.into(),
+        }
+    }
+}
+
 /// The `Repeat` function returns a string consisting of the value string
 /// repeated number times.
 ///
@@ -306,6 +420,26 @@ impl<'a> Space<'a> {
     }
 }
 
+/// The `StartsWith` function returns `true` if the string starts with the
+/// `search` string.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/startswith)
+#[derive(Serialize, Clone, Debug)]
+pub struct StartsWith<'a> {
+    startswith: Expr<'a>,
+    search: Expr<'a>,
+}
+
+impl<'a> StartsWith<'a> {
+    pub fn new(value: impl Into<Expr<'a>>, search: impl Into<Expr<'a>>) -> Self {
+        Self {
+            startswith: value.into(),
+            search: search.into(),
+        }
+    }
+}
+
 /// The `SubString` function returns a portion of the `value` string beginning
 /// at the character `start` position for `length` characters long.
 ///
@@ -393,6 +527,22 @@ mod tests {
     use crate::prelude::*;
     use serde_json::{self, json};
 
+    #[test]
+    fn test_nested_string_functions() {
+        let mut fun = SubString::new(Concat::new(Array::from(vec!["me", "ow"]), ""), 1);
+        fun.length(2);
+
+        let serialized = serde_json::to_value(Query::from(fun)).unwrap();
+
+        let expected = json!({
+            "substring": {"concat": ["me", "ow"], "separator": ""},
+            "start": 1,
+            "length": 2,
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_case_fold() {
         let mut fun = CaseFold::new("Hen Wen");
@@ -409,6 +559,27 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_normalizer_strings() {
+        for normalizer in &[
+            Normalizer::NFKCCaseFold,
+            Normalizer::NFC,
+            Normalizer::NFD,
+            Normalizer::NFKC,
+            Normalizer::NFKD,
+        ] {
+            let name = normalizer.to_string();
+
+            assert_eq!(json!(name), serde_json::to_value(normalizer).unwrap());
+            assert_eq!(
+                json!(normalizer),
+                json!(name.parse::<Normalizer>().unwrap())
+            );
+        }
+
+        assert!("nfc".parse::<Normalizer>().is_err());
+    }
+
     #[test]
     fn test_concat() {
         let fun = Concat::new(Array::from(vec!["Hen", "Wen"]), ",");
@@ -424,6 +595,21 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_ends_with() {
+        let fun = EndsWith::new("Musti", "ti");
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "endswith": "Musti",
+            "search": "ti",
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_find_str() {
         let mut fun = FindStr::new("fire and fireman", "fire");
@@ -460,6 +646,24 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_format() {
+        let fun = Format::new(
+            "%s has %d lives",
+            Array::from(vec![Expr::from("Musti"), Expr::from(9)]),
+        );
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "format": "%s has %d lives",
+            "values": ["Musti", 9],
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_ltrim() {
         let fun = LTrim::new("     haha");
@@ -516,6 +720,41 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_ngram() {
+        let query = Query::from(NGram::new("meow"));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(json!({"ngram": "meow"}), serialized);
+    }
+
+    #[test]
+    fn test_ngram_min_max() {
+        let mut fun = NGram::new("meow");
+        fun.min(2).max(3);
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "ngram": "meow",
+            "min": 2,
+            "max": 3,
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_regex_escape() {
+        let fun = RegexEscape::new("cats (and dogs)");
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(json!({"regexescape": "cats (and dogs)"}), serialized);
+    }
+
     #[test]
     fn test_repeat() {
         let fun = Repeat::new("All work and no play makes Jack a dull boy.", 100000);
@@ -578,6 +817,21 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_starts_with() {
+        let fun = StartsWith::new("Musti", "Mu");
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "startswith": "Musti",
+            "search": "Mu",
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_substring() {
         let mut fun = SubString::new("meowmeowcat", 4);
@@ -1,5 +1,11 @@
 //! Write functions
-use crate::{expr::Expr, query::Query};
+use crate::{
+    expr::Expr,
+    query::{
+        read::{Get, Select},
+        Query,
+    },
+};
 use chrono::{DateTime, Utc};
 
 mod create;
@@ -22,7 +28,8 @@ pub use update::*;
 
 query![Delete, Remove, Replace];
 
-#[derive(Serialize, Debug, Clone, Copy)]
+/// The action of an event in the history of an instance or a set.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
 pub enum Action {
     #[serde(rename = "create")]
     Create,
@@ -30,8 +37,22 @@ pub enum Action {
     Delete,
     #[serde(rename = "update")]
     Update,
+    /// An element was added to a set. Only found in set histories.
+    #[serde(rename = "add")]
+    Add,
+    /// An element was removed from a set. Only found in set histories.
+    #[serde(rename = "remove")]
+    Remove,
 }
 
+string_enum!(Action, "Unknown action", {
+    Create => "create",
+    Delete => "delete",
+    Update => "update",
+    Add => "add",
+    Remove => "remove",
+});
+
 /// The delete function removes an object. Some of the common objects to delete
 /// are instances, classes, indexes and databases.
 ///
@@ -48,6 +69,12 @@ impl<'a> Delete<'a> {
             delete: reference.into(),
         }
     }
+
+    /// Deletes the first instance matching the `terms` in the `index`, in one
+    /// query.
+    pub fn by_match(index: impl Into<Expr<'a>>, terms: impl Into<Expr<'a>>) -> Self {
+        Self::new(Select::new(vec!["ref"], Get::by_match(index, terms)))
+    }
 }
 
 /// The `Remove` function deletes an event from an instance’s history.
@@ -99,10 +126,28 @@ impl<'a> Replace<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::prelude::*;
+    use crate::{prelude::*, test_utils::*};
     use chrono::{offset::TimeZone, Utc};
     use serde_json::{self, json};
 
+    #[test]
+    fn test_action_strings() {
+        for action in &[
+            Action::Create,
+            Action::Delete,
+            Action::Update,
+            Action::Add,
+            Action::Remove,
+        ] {
+            let name = action.to_string();
+
+            assert_eq!(json!(name), serde_json::to_value(action).unwrap());
+            assert_eq!(*action, name.parse().unwrap());
+        }
+
+        assert!("rename".parse::<Action>().is_err());
+    }
+
     #[test]
     fn test_delete() {
         let delete = Delete::new(Ref::instance("musti"));
@@ -120,11 +165,65 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_delete_by_match() {
+        let delete = Delete::by_match(Index::find("cats_by_name"), "Musti");
+        let query = Query::from(delete);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "delete": {
+                "select": ["ref"],
+                "from": {
+                    "get": {"match": {"index": "cats_by_name"}, "terms": "Musti"}
+                }
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_delete_by_match_eval() {
+        with_class(|client, class_name| {
+            let index_name = gen_db_name();
+
+            let mut params = IndexParams::new(&index_name, Ref::class(class_name));
+            params.terms(vec![Term::field(vec!["data", "name"])]);
+            params.unique();
+
+            client.query(CreateIndex::new(params)).unwrap();
+
+            let mut data = Object::default();
+            data.insert("name", "Musti");
+
+            client
+                .query(Create::new(Ref::class(class_name), data))
+                .unwrap();
+
+            let exists = |client: &SyncClient| {
+                let response = client
+                    .query(Exists::by_match(Ref::index(&index_name), "Musti"))
+                    .unwrap();
+
+                response.resource.as_bool().unwrap()
+            };
+
+            assert!(exists(client));
+
+            client
+                .query(Delete::by_match(Ref::index(&index_name), "Musti"))
+                .unwrap();
+
+            assert!(!exists(client));
+        });
+    }
+
     #[test]
     fn test_remove() {
         let fun = Remove::new(
             Ref::instance("naukio"),
-            Utc.timestamp(60, 0),
+            Utc.timestamp_opt(60, 0).unwrap(),
             Action::Create,
         );
 
@@ -171,4 +270,58 @@ mod tests {
 
         assert_eq!(expected, serialized);
     }
+
+    #[test]
+    fn test_insert_and_remove_eval() {
+        let mut data = Object::default();
+        data.insert("name", "Musti");
+
+        with_class(|client, class_name| {
+            let response = client
+                .query(Create::new(Class::find(class_name), data))
+                .unwrap();
+
+            let reference = response.resource.get_reference().unwrap().clone();
+            let event_ts = Utc.timestamp_opt(60, 0).unwrap();
+
+            let mut event_data = Object::default();
+            event_data.insert("name", "Naukio");
+
+            let params = InsertParams::new(event_data, Expr::null(), Expr::null());
+
+            client
+                .query(Insert::new(
+                    reference.clone(),
+                    event_ts,
+                    Action::Update,
+                    params,
+                ))
+                .unwrap();
+
+            let find_event = |reference: &Ref<'static>| {
+                let mut history = Paginate::new(reference.clone());
+                history.events(true);
+
+                let response = client.query(history).unwrap();
+
+                response.resource["data"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .find(|event| event["ts"].as_u64() == Some(60_000_000))
+                    .cloned()
+            };
+
+            let event = find_event(&reference).unwrap();
+
+            assert_eq!(Some("update"), event["action"].as_str());
+            assert_eq!(Some("Naukio"), event["data"]["name"].as_str());
+
+            client
+                .query(Remove::new(reference.clone(), event_ts, Action::Update))
+                .unwrap();
+
+            assert_eq!(None, find_event(&reference));
+        });
+    }
 }
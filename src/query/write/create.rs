@@ -97,39 +97,39 @@ mod tests {
         obj.insert("byte_data", Bytes::from(vec![0x1, 0x2, 0x3]));
         obj.insert("nicknames", Array::from(nickname_vals.clone()));
         obj.insert("am_i_cute", true);
-        obj.insert("created_at", Utc.timestamp(60, 0));
-        obj.insert("birthday", NaiveDate::from_ymd(2011, 7, 7));
+        obj.insert("created_at", Utc.timestamp_opt(60, 0).unwrap());
+        obj.insert("birthday", NaiveDate::from_ymd_opt(2011, 7, 7).unwrap());
 
-        with_class(|class_name| {
-            let response = CLIENT
+        with_class(|client, class_name| {
+            let response = client
                 .query(Create::new(Class::find(class_name), obj))
                 .unwrap();
 
-            let res = response.resource;
+            let res = response.data().unwrap();
 
-            assert_eq!(res["data"]["name"].as_str(), Some("Musti"));
-            assert_eq!(res["data"]["id"].as_u64(), Some(1));
-            assert_eq!(res["data"]["age"].as_u64(), Some(7));
-            assert_eq!(res["data"]["am_i_cute"].as_bool(), Some(true));
+            assert_eq!(res["name"].as_str(), Some("Musti"));
+            assert_eq!(res["id"].as_u64(), Some(1));
+            assert_eq!(res["age"].as_u64(), Some(7));
+            assert_eq!(res["am_i_cute"].as_bool(), Some(true));
 
             assert_eq!(
-                res["data"]["byte_data"].as_bytes(),
+                res["byte_data"].as_bytes(),
                 Some(&Bytes::from(vec![0x1, 0x2, 0x3]))
             );
 
             assert_eq!(
-                res["data"]["created_at"].as_timestamp(),
-                Some(Utc.timestamp(60, 0))
+                res["created_at"].as_timestamp(),
+                Some(Utc.timestamp_opt(60, 0).unwrap())
             );
 
             assert_eq!(
-                res["data"]["birthday"].as_date(),
-                Some(NaiveDate::from_ymd(2011, 7, 7))
+                res["birthday"].as_date(),
+                Some(NaiveDate::from_ymd_opt(2011, 7, 7).unwrap())
             );
 
-            assert_eq!(res["data"]["nicknames"][0].as_str(), Some("mustu"));
-            assert_eq!(res["data"]["nicknames"][1].as_str(), Some("muspus"));
-            assert_eq!(res["data"]["nicknames"][2].as_str(), Some("mustikka"));
+            assert_eq!(res["nicknames"][0].as_str(), Some("mustu"));
+            assert_eq!(res["nicknames"][1].as_str(), Some("muspus"));
+            assert_eq!(res["nicknames"][2].as_str(), Some("mustikka"));
         });
     }
 }
@@ -139,8 +139,8 @@ mod tests {
         params.permissions(permission);
         params.data(data);
 
-        with_database(|_| {
-            let response = CLIENT.query(CreateClass::new(params)).unwrap();
+        with_scoped_client(|client, _| {
+            let response = client.query(CreateClass::new(params)).unwrap();
             let res = response.resource;
 
             assert_eq!(res["history_days"].as_u64(), Some(10));
@@ -82,9 +82,8 @@ impl<'a> DatabaseParams<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{prelude::*, test_utils::CLIENT};
+    use crate::{prelude::*, test_utils::*};
     use serde_json::{self, json};
-    use std::panic;
 
     #[test]
     fn test_create_database_expr() {
@@ -112,31 +111,27 @@ mod tests {
         let mut data = Object::default();
         data.insert("foo", "bar");
 
-        let db_name = "test";
-        let mut params = DatabaseParams::new(db_name);
+        let db_name = gen_db_name();
+        let mut params = DatabaseParams::new(&db_name);
         params.priority(10).unwrap();
         params.data(data);
 
-        let result = panic::catch_unwind(|| {
-            let response = CLIENT.query(CreateDatabase::new(params)).unwrap();
+        with_scoped_client(|client, _| {
+            let response = client.query(CreateDatabase::new(params)).unwrap();
             let res = response.resource;
 
             assert_eq!(res["api_version"].as_str(), Some("2.0"));
-            assert_eq!(res["name"].as_str(), Some(db_name));
+            assert_eq!(res["name"].as_str(), Some(db_name.as_str()));
             assert_eq!(res["priority"].as_u64(), Some(10));
 
             assert_eq!(
                 res["ref"].as_reference().unwrap().path(),
-                Ref::database(db_name).path()
+                Ref::database(&db_name).path()
             );
 
             assert_eq!(res["data"]["foo"].as_str(), Some("bar"));
 
             assert!(res["ts"].is_number());
         });
-
-        CLIENT.query(Delete::new(Ref::database(db_name))).unwrap();
-
-        result.unwrap();
     }
 }
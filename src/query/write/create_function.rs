@@ -1,5 +1,5 @@
 use crate::{
-    expr::{Expr, Object},
+    expr::{AnnotatedExpr, Expr, Object},
     query::Query,
 };
 use std::borrow::Cow;
@@ -52,6 +52,25 @@ impl<'a> FunctionParams<'a> {
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.object.name
+    }
+
+    /// The number of arguments the function takes, if the body is a `Lambda`
+    /// with a known [arity](../basic/struct.Lambda.html#method.arity).
+    pub fn arity(&self) -> Option<usize> {
+        match self.object.body {
+            Expr::Annotated(AnnotatedExpr::Quote(ref body)) => match **body {
+                Expr::Query(ref query) => match **query {
+                    Query::Lambda(ref lambda) => lambda.arity(),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     /// User-defined metadata for the function. It is provided for the
     /// developer to store information at the function level.
     pub fn data(&mut self, data: Object<'a>) -> &mut Self {
@@ -84,11 +84,43 @@ pub struct IndexValue<'a> {
     object: ValueObject<'a>,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[doc(hidden)]
+pub struct SourceObject<'a> {
+    class: Expr<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fields: Option<SourceFields<'a>>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[doc(hidden)]
+pub struct SourceFields<'a> {
+    object: Object<'a>,
+}
+
+/// Source objects describe the classes the index gets its instances from, and
+/// the bindings computed for the instances of each class.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/indexconfig#source-objects)
+#[derive(Debug, Serialize, Clone)]
+pub struct IndexSource<'a> {
+    object: SourceObject<'a>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(untagged)]
+#[doc(hidden)]
+pub enum IndexSources<'a> {
+    Single(Expr<'a>),
+    Multiple(Vec<IndexSource<'a>>),
+}
+
 #[derive(Debug, Serialize, Clone)]
 #[doc(hidden)]
 pub struct IndexParamsInternal<'a> {
     name: Cow<'a, str>,
-    source: Expr<'a>,
+    source: IndexSources<'a>,
     active: bool,
     unique: bool,
     serialized: bool,
@@ -175,6 +207,39 @@ impl<'a> IndexValue<'a> {
     }
 }
 
+impl<'a> IndexSource<'a> {
+    /// Index the instances of the given class `Ref`.
+    pub fn new(class: impl Into<Expr<'a>>) -> Self {
+        Self {
+            object: SourceObject {
+                class: class.into(),
+                fields: None,
+            },
+        }
+    }
+
+    /// Index the instances of all classes.
+    pub fn wildcard() -> Self {
+        Self::new("_")
+    }
+
+    /// A binding computed for the instances of the class with the given
+    /// function, usually a [Lambda](../basic/struct.Lambda.html) taking the
+    /// instance. The binding can be used in `Term::binding` and
+    /// `IndexValue::binding`.
+    ///
+    /// The function is quoted, so it is stored in the index instead of being
+    /// evaluated in the query.
+    pub fn binding(&mut self, name: &'a str, function: impl Into<Expr<'a>>) -> &mut Self {
+        let fields = self.object.fields.get_or_insert_with(|| SourceFields {
+            object: Object::default(),
+        });
+
+        fields.object.insert(name, function.into().into_quoted());
+        self
+    }
+}
+
 impl<'a> IndexParams<'a> {
     /// The name cannot be `events`, `sets`, `self`, `instances` or `_`.
     ///
@@ -186,7 +251,7 @@ impl<'a> IndexParams<'a> {
         Self {
             object: IndexParamsInternal {
                 name: name.into(),
-                source: source.into(),
+                source: IndexSources::Single(source.into()),
                 active: false,
                 unique: false,
                 serialized: false,
@@ -199,6 +264,13 @@ impl<'a> IndexParams<'a> {
         }
     }
 
+    /// Index instances from multiple classes, replacing the source given in
+    /// `new`.
+    pub fn sources(&mut self, sources: Vec<IndexSource<'a>>) -> &mut Self {
+        self.object.source = IndexSources::Multiple(sources);
+        self
+    }
+
     /// If set, avoids building the index from relevant instances.
     pub fn active(&mut self) -> &mut Self {
         self.object.active = true;
@@ -334,4 +406,85 @@ mod tests {
 
         assert_eq!(expected, serialized);
     }
+
+    #[test]
+    fn test_create_index_with_multiple_sources() {
+        let mut cats = IndexSource::new(Ref::class("cats"));
+        cats.binding(
+            "pet_name",
+            Lambda::new("cat", Select::new(vec!["data", "name"], Var::new("cat"))),
+        );
+
+        let dogs = IndexSource::new(Ref::class("dogs"));
+
+        let mut params = IndexParams::new("pets_by_name", Ref::class("cats"));
+        params.sources(vec![cats, dogs]);
+        params.terms(vec![Term::binding("pet_name")]);
+
+        let query = Query::from(CreateIndex::new(params));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "create_index": {
+                "object": {
+                    "active": false,
+                    "name": "pets_by_name",
+                    "serialized": false,
+                    "source": [
+                        {
+                            "object": {
+                                "class": {
+                                    "@ref": {
+                                        "class": {"@ref": {"id": "classes"}},
+                                        "id": "cats",
+                                    }
+                                },
+                                "fields": {
+                                    "object": {
+                                        "pet_name": {
+                                            "@query": {
+                                                "lambda": "cat",
+                                                "expr": {
+                                                    "select": ["data", "name"],
+                                                    "from": {"var": "cat"},
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        {
+                            "object": {
+                                "class": {
+                                    "@ref": {
+                                        "class": {"@ref": {"id": "classes"}},
+                                        "id": "dogs",
+                                    }
+                                }
+                            }
+                        },
+                    ],
+                    "terms": [
+                        {
+                            "object": {
+                                "binding": "pet_name",
+                            }
+                        },
+                    ],
+                    "unique": false,
+                }
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_wildcard_index_source() {
+        let source = IndexSource::wildcard();
+        let serialized = serde_json::to_value(&source).unwrap();
+
+        assert_eq!(json!({"object": {"class": "_"}}), serialized);
+    }
 }
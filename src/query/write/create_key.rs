@@ -1,11 +1,11 @@
 use crate::{
-    expr::{Expr, Object, Ref},
+    expr::{Expr, Object},
     query::Query,
 };
 
 boxed_query!(CreateKey);
 
-#[derive(Debug, Serialize, Clone, Copy)]
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
 pub enum Role {
     #[serde(rename = "admin")]
     Admin,
@@ -17,6 +17,13 @@ pub enum Role {
     Client,
 }
 
+string_enum!(Role, "Unknown role", {
+    Admin => "admin",
+    Server => "server",
+    ServerReadOnly => "server-readonly",
+    Client => "client",
+});
+
 /// `CreateKey` creates a new key to access a database with the specified
 /// `param_object`. It requires an admin key for authentication.
 ///
@@ -53,10 +60,13 @@ impl<'a> KeyParams<'a> {
     ///
     /// * A reference to the database for which a key should be created.
     /// * The access role
-    pub fn new(database: Ref<'a>, role: Role) -> Self {
+    ///
+    /// The database can also be an expression evaluating to a database
+    /// `Ref`.
+    pub fn new(database: impl Into<Expr<'a>>, role: Role) -> Self {
         Self {
             object: KeyParamsInternal {
-                database: Expr::from(database),
+                database: database.into(),
                 role,
                 priority: Expr::from(1),
                 data: None,
@@ -85,6 +95,23 @@ mod tests {
     use crate::prelude::*;
     use serde_json::{self, json};
 
+    #[test]
+    fn test_role_strings() {
+        for role in &[
+            Role::Admin,
+            Role::Server,
+            Role::ServerReadOnly,
+            Role::Client,
+        ] {
+            let name = role.to_string();
+
+            assert_eq!(json!(name), serde_json::to_value(role).unwrap());
+            assert_eq!(*role, name.parse().unwrap());
+        }
+
+        assert!("root".parse::<Role>().is_err());
+    }
+
     #[test]
     fn test_create_index() {
         let mut data = Object::default();
@@ -86,7 +86,7 @@ mod tests {
 
         let fun = Insert::new(
             Ref::instance("musti"),
-            Utc.timestamp(60, 0),
+            Utc.timestamp_opt(60, 0).unwrap(),
             Action::Update,
             params,
         );
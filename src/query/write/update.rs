@@ -1,4 +1,7 @@
-use crate::{expr::Expr, query::Query};
+use crate::{
+    expr::{Expr, Object},
+    query::Query,
+};
 
 query!(Update);
 
@@ -59,6 +62,15 @@ impl<'a> UpdateParams<'a> {
         self
     }
 
+    /// Set a new password in the credentials. Overrides any other
+    /// `credentials`.
+    pub fn password(&mut self, password: impl Into<Expr<'a>>) -> &mut Self {
+        let mut credentials = Object::default();
+        credentials.insert("password", password.into());
+
+        self.credentials(credentials)
+    }
+
     pub fn delegates(&mut self, delegates: impl Into<Expr<'a>>) -> &mut Self {
         self.object.delegates = Some(delegates.into());
         self
@@ -120,4 +132,21 @@ mod tests {
 
         assert_eq!(expected, serialized);
     }
+
+    #[test]
+    fn test_password() {
+        let mut params = UpdateParams::new();
+        params.password("meowmeow");
+
+        let query = Query::from(Update::new(Ref::instance("musti"), params));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "object": {
+                "credentials": {"object": {"password": "meowmeow"}}
+            }
+        });
+
+        assert_eq!(expected, serialized["params"]);
+    }
 }
@@ -1,19 +1,22 @@
 use crate::expr::Bytes;
+use base64::display::Base64Display;
 use serde::{de, ser};
 use std::fmt;
 
+/// Encodes the bytes straight into the serializer, without allocating the
+/// base64 string first when the serializer supports it (`serde_json` does).
 pub fn serialize<'a, S>(data: &Bytes<'a>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: ser::Serializer,
 {
-    serializer.serialize_str(&base64::encode(&data.0))
+    serializer.collect_str(&Base64Display::with_config(&data.0, base64::STANDARD))
 }
 
 pub fn deserialize<'a, 'de, D>(d: D) -> Result<Bytes<'a>, D::Error>
 where
     D: de::Deserializer<'de>,
 {
-    Ok(d.deserialize_str(Base64BytesVisitor)?)
+    d.deserialize_str(Base64BytesVisitor)
 }
 
 struct Base64BytesVisitor;
@@ -31,7 +34,7 @@ impl<'de> de::Visitor<'de> for Base64BytesVisitor {
     {
         base64::decode(value)
             .map_err(|err| de::Error::custom(err.to_string()))
-            .map(|bytes| Bytes::from(bytes.to_vec()))
+            .map(Bytes::from)
     }
 
     fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
@@ -40,6 +43,29 @@ impl<'de> de::Visitor<'de> for Base64BytesVisitor {
     {
         base64::decode(value.as_str())
             .map_err(|err| de::Error::custom(err.to_string()))
-            .map(|bytes| Bytes::from(bytes.to_vec()))
+            .map(Bytes::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use serde_json;
+
+    #[test]
+    fn test_large_borrowed_bytes() {
+        let blob: Vec<u8> = (0..4 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+
+        let expr = Expr::from(Bytes::from(&blob[..]));
+        let serialized = serde_json::to_string(&expr).unwrap();
+
+        assert_eq!(
+            format!("{{\"@bytes\":\"{}\"}}", base64::encode(&blob)),
+            serialized
+        );
+
+        let value: Value = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(Some(&Bytes::from(blob)), value.as_bytes());
     }
 }
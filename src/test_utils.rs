@@ -1,13 +1,22 @@
 use crate::prelude::*;
+use futures::{future, Future, Stream};
+use hyper::{Body, Request, Response, StatusCode};
 use lazy_static::lazy_static;
 use rand::distributions::Alphanumeric;
 use rand::Rng;
-use std::panic;
+use std::{
+    panic,
+    sync::{Arc, Mutex},
+};
+
+const FAUNA_URI: &str = "http://localhost:8443";
 
 lazy_static! {
+    /// A client with the admin key of the server, for managing the test
+    /// databases.
     pub static ref CLIENT: SyncClient = {
         let mut builder = Client::builder("secret");
-        builder.uri("http://localhost:8443");
+        builder.uri(FAUNA_URI);
 
         builder.build_sync().unwrap()
     };
@@ -20,9 +29,11 @@ pub fn gen_db_name() -> String {
         .collect()
 }
 
+/// Runs `f` with the name of a new, uniquely named database. The database is
+/// deleted afterwards, even if `f` panics.
 pub fn with_database<F>(f: F)
 where
-    F: FnOnce(&str) -> () + panic::UnwindSafe,
+    F: FnOnce(&str) + panic::UnwindSafe,
 {
     let db_name = gen_db_name();
     let params = DatabaseParams::new(&db_name);
@@ -38,32 +49,157 @@ where
     result.unwrap();
 }
 
+/// Runs `f` with a client using an admin key of a new, uniquely named
+/// database, and the name of the database. Everything `f` creates with the
+/// client lives in the database, which is deleted afterwards together with
+/// the key.
+pub fn with_scoped_client<F>(f: F)
+where
+    F: FnOnce(&SyncClient, &str) + panic::UnwindSafe,
+{
+    with_database(|db_name| {
+        let params = KeyParams::new(Ref::database(db_name), Role::Admin);
+
+        trace!("Creating a key for the test database {}", db_name);
+        let response = CLIENT.query(CreateKey::new(params)).unwrap();
+        let secret = response.resource["secret"].as_str().unwrap();
+
+        let mut builder = Client::builder(secret);
+        builder.uri(FAUNA_URI);
+
+        f(&builder.build_sync().unwrap(), db_name)
+    })
+}
+
+/// Runs `f` with a scoped client and the name of a new class in its database.
 pub fn with_class<F>(f: F)
 where
-    F: FnOnce(&str) -> () + panic::UnwindSafe,
+    F: FnOnce(&SyncClient, &str) + panic::UnwindSafe,
 {
-    let mut permission = ClassPermission::default();
-    permission.read(Level::public());
-    permission.write(Level::public());
+    with_scoped_client(|client, _| {
+        let mut permission = ClassPermission::default();
+        permission.read(Level::public());
+        permission.write(Level::public());
 
-    let mut data = Object::default();
-    data.insert("meow", true);
+        let mut data = Object::default();
+        data.insert("meow", true);
 
-    let class_name = gen_db_name();
+        let class_name = gen_db_name();
 
-    let mut params = ClassParams::new(&class_name);
-    params.history_days(10);
-    params.ttl_days(3);
-    params.permissions(permission);
-    params.data(data);
+        let mut params = ClassParams::new(&class_name);
+        params.history_days(10);
+        params.ttl_days(3);
+        params.permissions(permission);
+        params.data(data);
 
-    with_database(|_| {
         trace!("Creating a test class {}", &class_name);
-        CLIENT.query(CreateClass::new(params)).unwrap();
-
-        f(class_name.as_str());
+        client.query(CreateClass::new(params)).unwrap();
 
-        trace!("Creating the test class {}", &class_name);
-        CLIENT.query(Delete::new(Ref::class(&class_name))).unwrap();
+        f(client, class_name.as_str());
     })
 }
+
+pub type RecordedRequests = Arc<Mutex<Vec<Request<String>>>>;
+
+/// A transport answering the requests with canned responses, and recording
+/// the requests it got.
+pub struct MockTransport {
+    headers: Vec<(&'static str, String)>,
+    responses: Vec<(StatusCode, String)>,
+    requests: RecordedRequests,
+}
+
+impl MockTransport {
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            headers: Vec::new(),
+            responses: vec![(StatusCode::from_u16(status).unwrap(), body.into())],
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// A handle to the requests sent through the transport.
+    pub fn requests(&self) -> RecordedRequests {
+        self.requests.clone()
+    }
+
+    /// The body for the next request. The last body is repeated for the
+    /// rest of the requests.
+    pub fn then(mut self, body: impl Into<String>) -> Self {
+        let status = self.responses[0].0;
+        self.responses.push((status, body.into()));
+        self
+    }
+
+    /// Like `then`, but with the given status.
+    pub fn then_status(mut self, status: u16, body: impl Into<String>) -> Self {
+        self.responses
+            .push((StatusCode::from_u16(status).unwrap(), body.into()));
+        self
+    }
+
+    pub fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+}
+
+impl Transport for MockTransport {
+    fn call(&self, request: Request<Body>) -> TransportFuture {
+        let (parts, body) = request.into_parts();
+        let body = body.concat2().wait().unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        let mut requests = self.requests.lock().unwrap();
+        let (status, response_body) = &self.responses[requests.len().min(self.responses.len() - 1)];
+
+        requests.push(Request::from_parts(parts, body));
+
+        let mut builder = Response::builder();
+        builder.status(*status);
+
+        for (name, value) in self.headers.iter() {
+            builder.header(*name, value.as_str());
+        }
+
+        Box::new(future::ok(
+            builder.body(Body::from(response_body.clone())).unwrap(),
+        ))
+    }
+}
+
+pub fn mock_client<F>(transport: MockTransport, f: F) -> SyncClient
+where
+    F: FnOnce(&mut ClientBuilder),
+{
+    let mut builder = Client::builder("secret");
+    builder.transport(transport);
+    f(&mut builder);
+
+    builder.build_sync().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_scoped_client_eval() {
+        with_scoped_client(|client, db_name| {
+            let class_name = gen_db_name();
+
+            client
+                .query(CreateClass::new(ClassParams::new(&class_name)))
+                .unwrap();
+
+            let response = client.query(Exists::new(Ref::class(&class_name))).unwrap();
+            assert_eq!(Some(true), response.resource.as_bool());
+
+            let response = CLIENT.query(Exists::new(Ref::class(&class_name))).unwrap();
+            assert_eq!(Some(false), response.resource.as_bool());
+
+            let response = CLIENT.query(Exists::new(Ref::database(db_name))).unwrap();
+            assert_eq!(Some(true), response.resource.as_bool());
+        })
+    }
+}